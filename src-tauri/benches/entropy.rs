@@ -0,0 +1,47 @@
+//! Compares the entropy of every guess computed from the responses and read from a
+//! precomputed `PatternTable`. Run with `cargo bench --bench entropy`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use wordle_helper_lib::{
+    game_logic::compute_entropy, pattern_table::PatternTable, word_bank::WordBank, Word,
+};
+
+/// Number of words of the bank, the table takes the square of it in bytes.
+const BANK_SIZE: usize = 2000;
+
+fn main() {
+    let words: Vec<String> = WordBank::load()
+        .words()
+        .iter()
+        .take(BANK_SIZE)
+        .cloned()
+        .collect();
+    let bank = WordBank::from_words(words);
+    let guesses: Vec<Word> = bank
+        .words()
+        .iter()
+        .filter_map(|word| Word::new(word).ok())
+        .collect();
+
+    let start = Instant::now();
+    let total: f64 = guesses
+        .iter()
+        .map(|guess| compute_entropy(guess, bank.words()))
+        .sum();
+    println!("without table: {:?}", start.elapsed());
+    black_box(total);
+
+    let start = Instant::now();
+    let table = PatternTable::build(&bank);
+    println!("table build: {:?}", start.elapsed());
+
+    let candidates: Vec<usize> = (0..table.len()).collect();
+    let start = Instant::now();
+    let total: f64 = (0..table.len())
+        .map(|guess_idx| table.entropy(guess_idx, &candidates))
+        .sum();
+    println!("with table: {:?}", start.elapsed());
+    black_box(total);
+}
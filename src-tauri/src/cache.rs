@@ -0,0 +1,125 @@
+//! Module containing a cache of filtering results, for strategies filtering the same
+//! patterns again and again.
+
+use std::num::NonZeroUsize;
+
+use lru::LruCache;
+
+use crate::{game_logic::filter_words, Word};
+
+/// Number of pattern lists kept by `WordFilterCache::default`.
+pub const DEFAULT_CACHE_CAPACITY: usize = 1024;
+
+/// # `WordFilterCache`
+/// The most recently filtered pattern lists with their matching words.
+/// A cache is only valid for a single word list.
+#[derive(Debug)]
+pub struct WordFilterCache {
+    results: LruCache<Vec<Word>, Vec<String>>,
+    hits: usize,
+    misses: usize,
+}
+
+impl WordFilterCache {
+    /// # `new`
+    /// Creates an empty cache.
+    ///
+    /// ## Arguments
+    /// * `capacity` - The number of pattern lists to keep.
+    #[must_use]
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            results: LruCache::new(capacity),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// # `hit_rate`
+    /// Returns the share of lookups answered from the cache, between 0 and 1.
+    #[must_use]
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0.0;
+        }
+
+        // Lookup counts stay far below 2^52, the conversions are exact
+        self.hits as f64 / lookups as f64
+    }
+}
+
+impl Default for WordFilterCache {
+    fn default() -> Self {
+        Self::new(NonZeroUsize::new(DEFAULT_CACHE_CAPACITY).unwrap_or(NonZeroUsize::MIN))
+    }
+}
+
+/// # `filter_words_cached`
+/// Filters a list of words based on a list of patterns, reusing the result of a
+/// previous call with the same patterns.
+///
+/// ## Arguments
+/// * `all_words` - The list of words to filter, always the same for a given cache.
+/// * `patterns` - The list of patterns to filter against.
+/// * `cache` - The cache of previous results.
+///
+/// ## Returns
+/// * `Vec<String>` - The filtered list of words.
+pub fn filter_words_cached(
+    all_words: &[String],
+    patterns: &[Word],
+    cache: &mut WordFilterCache,
+) -> Vec<String> {
+    if let Some(words) = cache.results.get(patterns) {
+        cache.hits += 1;
+        return words.clone();
+    }
+
+    cache.misses += 1;
+    let words = filter_words(all_words, patterns);
+    cache.results.put(patterns.to_vec(), words.clone());
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_logic::compute_response;
+
+    #[test]
+    fn test_filter_words_cached() {
+        let all_words: Vec<String> = ["crane", "slate", "trace", "react", "caret", "pious"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let mut cache = WordFilterCache::default();
+
+        // Evaluating an opener twice against every secret, the second run only hits
+        let opener = Word::new("slate").unwrap();
+        for _ in 0..2 {
+            for secret in &all_words {
+                let pattern = compute_response(&opener, &Word::new(secret).unwrap());
+                let words =
+                    filter_words_cached(&all_words, std::slice::from_ref(&pattern), &mut cache);
+                assert_eq!(words, filter_words(&all_words, &[pattern]));
+            }
+        }
+        assert!((cache.hit_rate() - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let all_words = vec!["crane".to_string(), "slate".to_string()];
+        let mut cache = WordFilterCache::new(NonZeroUsize::new(1).unwrap());
+        let crane = Word::from_pattern_code("crane", "GGGGG").unwrap();
+        let slate = Word::from_pattern_code("slate", "GGGGG").unwrap();
+
+        filter_words_cached(&all_words, std::slice::from_ref(&crane), &mut cache);
+        filter_words_cached(&all_words, &[slate], &mut cache);
+        // "crane" was evicted by "slate"
+        filter_words_cached(&all_words, &[crane], &mut cache);
+        assert!(cache.hit_rate().abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,179 @@
+//! Module containing the configuration of the application.
+
+#[cfg(feature = "config")]
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "config")]
+use crate::WordleError;
+use crate::{
+    game_state::{GameMode, DEFAULT_MAX_GUESSES},
+    WORDS_FILE,
+};
+
+/// Number of letters of the words, the only length the game supports.
+pub const WORD_LENGTH: usize = 5;
+
+/// # `ColorScheme`
+/// The colors the letter states are shown with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorScheme {
+    #[default]
+    Default, // Green and yellow, as in Wordle
+    HighContrast, // Orange and blue, for colorblind players
+    Accessible,   // Distinct shapes, readable without colors
+}
+
+/// # `WordleConfig`
+/// The settings new games are created with.
+/// Missing fields of a configuration file take their default value.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct WordleConfig {
+    pub words_file: PathBuf, // JSON array of words, relative to the working directory
+    pub word_length: usize,
+    pub max_guesses: usize,
+    pub game_mode: GameMode,
+    pub language: String,
+    pub color_scheme: ColorScheme,
+}
+
+impl Default for WordleConfig {
+    fn default() -> Self {
+        Self {
+            words_file: PathBuf::from(WORDS_FILE),
+            word_length: WORD_LENGTH,
+            max_guesses: DEFAULT_MAX_GUESSES,
+            game_mode: GameMode::Normal,
+            language: "en".to_string(),
+            color_scheme: ColorScheme::Default,
+        }
+    }
+}
+
+impl WordleConfig {
+    /// # `from_env`
+    /// Reads the configuration from the `WORDLE_WORDS_FILE`, `WORDLE_MAX_GUESSES` and
+    /// `WORDLE_GAME_MODE` environment variables.
+    ///
+    /// ## Returns
+    /// * `WordleConfig` - The configuration, with the default value of every variable
+    ///   that is not set or invalid.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let mut config = Self::default();
+        if let Some(words_file) = var("WORDLE_WORDS_FILE") {
+            config.words_file = PathBuf::from(words_file);
+        }
+        if let Some(max_guesses) = var("WORDLE_MAX_GUESSES")
+            .and_then(|value| value.parse().ok())
+            .filter(|&max_guesses| max_guesses > 0)
+        {
+            config.max_guesses = max_guesses;
+        }
+        match var("WORDLE_GAME_MODE").as_deref() {
+            Some("normal") => config.game_mode = GameMode::Normal,
+            Some("hard") => config.game_mode = GameMode::Hard,
+            _ => {}
+        }
+
+        config
+    }
+}
+
+#[cfg(feature = "config")]
+impl WordleConfig {
+    /// # `from_toml`
+    /// Reads the configuration from a TOML file, e.g. `max_guesses = 8`.
+    ///
+    /// ## Arguments
+    /// * `path` - The configuration file, the default configuration is used if it
+    ///   does not exist.
+    ///
+    /// ## Returns
+    /// * `Result<WordleConfig, WordleError>` - The configuration or an error if the file
+    ///   cannot be read, parsed or has unsupported values.
+    pub fn from_toml(path: &Path) -> Result<Self, WordleError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path).map_err(|e| WordleError::Io(e.to_string()))?;
+        let config: Self =
+            toml::from_str(&content).map_err(|e| WordleError::InvalidConfig(e.to_string()))?;
+        if config.word_length != WORD_LENGTH {
+            return Err(WordleError::InvalidConfig(format!(
+                "only {WORD_LENGTH} letter words are supported, got {}",
+                config.word_length
+            )));
+        }
+        if config.max_guesses == 0 {
+            return Err(WordleError::InvalidConfig(
+                "at least one guess must be allowed".to_string(),
+            ));
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_vars() {
+        assert_eq!(WordleConfig::from_vars(|_| None), WordleConfig::default());
+
+        let config = WordleConfig::from_vars(|key| match key {
+            "WORDLE_WORDS_FILE" => Some("answers.json".to_string()),
+            "WORDLE_MAX_GUESSES" => Some("8".to_string()),
+            "WORDLE_GAME_MODE" => Some("hard".to_string()),
+            _ => None,
+        });
+        assert_eq!(config.words_file, PathBuf::from("answers.json"));
+        assert_eq!(config.max_guesses, 8);
+        assert_eq!(config.game_mode, GameMode::Hard);
+
+        let config = WordleConfig::from_vars(|key| match key {
+            "WORDLE_MAX_GUESSES" => Some("0".to_string()),
+            "WORDLE_GAME_MODE" => Some("expert".to_string()),
+            _ => None,
+        });
+        assert_eq!(config, WordleConfig::default());
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_from_toml() {
+        let path = std::env::temp_dir().join(format!("wordle_config_{}.toml", std::process::id()));
+        assert_eq!(WordleConfig::from_toml(&path), Ok(WordleConfig::default()));
+
+        std::fs::write(&path, "max_guesses = 8\ngame_mode = \"hard\"\n").unwrap();
+        let config = WordleConfig::from_toml(&path).unwrap();
+        assert_eq!(config.max_guesses, 8);
+        assert_eq!(config.game_mode, GameMode::Hard);
+        assert_eq!(config.words_file, PathBuf::from(WORDS_FILE));
+
+        std::fs::write(&path, "word_length = 6").unwrap();
+        assert!(matches!(
+            WordleConfig::from_toml(&path),
+            Err(WordleError::InvalidConfig(_))
+        ));
+
+        std::fs::write(&path, "max_guesses = \"six\"").unwrap();
+        assert!(matches!(
+            WordleConfig::from_toml(&path),
+            Err(WordleError::InvalidConfig(_))
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
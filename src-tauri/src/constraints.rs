@@ -0,0 +1,490 @@
+//! Module containing the constraints accumulated from a set of patterns.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{LetterState, Word};
+
+/// # `LetterConstraints`
+/// The constraints a candidate must satisfy, compiled from one or more patterns.
+///
+/// A game can accumulate them one guess at a time: `add_pattern` merges the new
+/// guess, then `filter_words_by_constraints` (or `is_satisfied_by` for a single word)
+/// only has to check the candidates left by the previous guesses.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LetterConstraints {
+    /// Letters known to be at a given position (Correct).
+    pub known: [Option<char>; 5],
+    /// Letters the answer must contain somewhere (Correct or Misplaced).
+    pub required: HashSet<char>,
+    /// Minimum occurrences of letters revealed several times in a pattern, e.g. 'e'
+    /// in "geese" with one Misplaced and one Correct appears at least twice.
+    pub min_count: HashMap<char, usize>,
+    /// Letters the answer does not contain at all.
+    pub forbidden: HashSet<char>,
+    /// Letters that cannot be at a given position (Misplaced or Absent there).
+    pub forbidden_at: [HashSet<char>; 5],
+    /// Maximum occurrences of letters capped by an Absent duplicate, e.g. 'p' in
+    /// "happy" with one 'p' Correct and the other Absent appears at most once.
+    pub max_count: HashMap<char, usize>,
+}
+
+impl LetterConstraints {
+    /// # `new`
+    /// Creates an empty set of constraints, satisfied by every word.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # `from_patterns`
+    /// Compiles the constraints of all the given patterns.
+    ///
+    /// ## Arguments
+    /// * `patterns` - The list of patterns to compile.
+    ///
+    /// ## Returns
+    /// * `LetterConstraints` - The combined constraints.
+    #[must_use]
+    pub fn from_patterns(patterns: &[Word]) -> Self {
+        let mut constraints = Self::new();
+        for pattern in patterns {
+            constraints.add_pattern(pattern);
+        }
+
+        constraints
+    }
+
+    /// # `add_pattern`
+    /// Adds the information of a pattern to the constraints.
+    ///
+    /// ## Arguments
+    /// * `pattern` - The pattern to add.
+    pub fn add_pattern(&mut self, pattern: &Word) {
+        for i in 0..5 {
+            let letter = pattern.letter_at(i);
+            match letter.state {
+                LetterState::Correct => {
+                    // Two different Correct letters at one position rule out every word
+                    if let Some(known) = self.known[i].filter(|&known| known != letter.character) {
+                        self.forbidden_at[i].extend([known, letter.character]);
+                    }
+                    self.known[i] = Some(letter.character);
+                    self.required.insert(letter.character);
+                }
+                LetterState::Misplaced => {
+                    self.forbidden_at[i].insert(letter.character);
+                    self.required.insert(letter.character);
+                }
+                LetterState::Absent => {
+                    self.forbidden_at[i].insert(letter.character);
+                }
+                LetterState::Unknown => {}
+            }
+        }
+
+        // Every occurrence marked present is a distinct copy in the answer
+        for i in 0..5 {
+            let letter = pattern.letter_at(i);
+            if !matches!(letter.state, LetterState::Correct | LetterState::Misplaced) {
+                continue;
+            }
+
+            let present = pattern.revealed_count(letter.character);
+            let min = self.min_count.entry(letter.character).or_insert(present);
+            *min = (*min).max(present);
+        }
+
+        // A letter marked absent caps its count to the occurrences marked present,
+        // so it is only forbidden everywhere if none of them is
+        for i in 0..5 {
+            let letter = pattern.letter_at(i);
+            if letter.state != LetterState::Absent {
+                continue;
+            }
+
+            let present = pattern.revealed_count(letter.character);
+            let max = self.max_count.entry(letter.character).or_insert(present);
+            *max = (*max).min(present);
+
+            if present == 0 && !self.required.contains(&letter.character) {
+                self.forbidden.insert(letter.character);
+            }
+        }
+    }
+
+    /// # `is_satisfied_by`
+    /// Checks if a candidate word satisfies every constraint.
+    ///
+    /// ## Arguments
+    /// * `candidate` - The word to check.
+    ///
+    /// ## Returns
+    /// * `bool` - `true` if the candidate satisfies the constraints, `false` otherwise.
+    #[must_use]
+    pub fn is_satisfied_by(&self, candidate: &Word) -> bool {
+        // Count the letters in the same pass, instead of once per counted letter
+        let mut counts = [0usize; 26];
+        for (i, letter) in candidate.into_iter().enumerate() {
+            let c = letter.character;
+            if self.known[i].is_some_and(|known| known != c)
+                || self.forbidden_at[i].contains(&c)
+                || self.forbidden.contains(&c)
+            {
+                return false;
+            }
+            counts[(c as u8 - b'a') as usize] += 1;
+        }
+        let count_of = |c: char| counts[(c as u8 - b'a') as usize];
+
+        self.required.iter().all(|&c| count_of(c) > 0)
+            && self.min_count.iter().all(|(&c, &min)| count_of(c) >= min)
+            && self.max_count.iter().all(|(&c, &max)| count_of(c) <= max)
+    }
+
+    /// # `covers`
+    /// Checks if these constraints imply `other`, i.e. if every word satisfying
+    /// `self` also satisfies `other`. This makes `other` redundant once `self` is known.
+    ///
+    /// The check is structural: a contradictory `self` is not detected and may
+    /// return `false` even though it is vacuously stricter.
+    ///
+    /// ## Arguments
+    /// * `other` - The constraints that may be implied.
+    ///
+    /// ## Returns
+    /// * `bool` - `true` if `self` is at least as strict as `other`, `false` otherwise.
+    #[must_use]
+    pub fn covers(&self, other: &LetterConstraints) -> bool {
+        let known_covered = (0..5).all(|i| match other.known[i] {
+            Some(c) => self.known[i] == Some(c),
+            None => true,
+        });
+
+        known_covered
+            && other.required.is_subset(&self.required)
+            && other
+                .min_count
+                .iter()
+                .all(|(&c, &min)| self.min_letter_count(c) >= min)
+            && other
+                .max_count
+                .iter()
+                .all(|(&c, &max)| self.max_letter_count(c).is_some_and(|cap| cap <= max))
+            && other
+                .forbidden
+                .iter()
+                .all(|&c| (0..5).all(|i| self.excludes_at(c, i)))
+            && (0..5).all(|i| {
+                other.forbidden_at[i]
+                    .iter()
+                    .all(|&c| self.excludes_at(c, i))
+            })
+    }
+
+    /// # `is_satisfiable`
+    /// Checks if any five letter string, in the word bank or not, satisfies every
+    /// constraint.
+    ///
+    /// ## Returns
+    /// * `bool` - `true` if the constraints can be satisfied, `false` if they contradict.
+    #[must_use]
+    pub fn is_satisfiable(&self) -> bool {
+        if self.required.iter().any(|&c| {
+            self.forbidden.contains(&c)
+                || self
+                    .max_count
+                    .get(&c)
+                    .is_some_and(|&max| max < self.min_letter_count(c))
+        }) {
+            return false;
+        }
+
+        let allowed: [Vec<char>; 5] =
+            std::array::from_fn(|i| ('a'..='z').filter(|&c| !self.excludes_at(c, i)).collect());
+        self.can_complete(&allowed, &mut ['a'; 5], 0)
+    }
+
+    // Depth first search of a string satisfying the constraints, from position `pos`
+    fn can_complete(&self, allowed: &[Vec<char>; 5], letters: &mut [char; 5], pos: usize) -> bool {
+        let placed = &letters[..pos];
+        let missing: usize = self
+            .required
+            .iter()
+            .map(|&c| {
+                let count = placed.iter().filter(|&&p| p == c).count();
+                self.min_letter_count(c).saturating_sub(count)
+            })
+            .sum();
+        if missing > 5 - pos {
+            return false;
+        }
+        if pos == 5 {
+            return true;
+        }
+
+        for &c in &allowed[pos] {
+            let count = letters[..pos].iter().filter(|&&p| p == c).count() + 1;
+            if self.max_count.get(&c).is_some_and(|&max| count > max) {
+                continue;
+            }
+
+            letters[pos] = c;
+            if self.can_complete(allowed, letters, pos + 1) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// # `min_letter_count`
+    /// Returns how many copies of a letter the answer contains at least.
+    ///
+    /// ## Arguments
+    /// * `c` - The letter to look up.
+    #[must_use]
+    pub fn min_letter_count(&self, c: char) -> usize {
+        let required = usize::from(self.required.contains(&c));
+        self.min_count
+            .get(&c)
+            .map_or(required, |&min| min.max(required))
+    }
+
+    /// # `max_letter_count`
+    /// Returns how many copies of a letter the answer contains at most.
+    ///
+    /// ## Arguments
+    /// * `c` - The letter to look up.
+    ///
+    /// ## Returns
+    /// * `Option<usize>` - The maximum, `None` if the count of the letter is not capped.
+    #[must_use]
+    pub fn max_letter_count(&self, c: char) -> Option<usize> {
+        if self.forbidden.contains(&c) {
+            return Some(0);
+        }
+
+        self.max_count.get(&c).copied()
+    }
+
+    /// Whether the constraints rule out letter `c` at position `pos`.
+    fn excludes_at(&self, c: char, pos: usize) -> bool {
+        self.forbidden.contains(&c)
+            || self.forbidden_at[pos].contains(&c)
+            || self.known[pos].is_some_and(|known| known != c)
+    }
+
+    /// # `summary`
+    /// Describes the constraints in a human-readable way, for debugging and display.
+    ///
+    /// ## Example
+    /// `Known: _r__e | Must include: {a≥1, r≥1} | Forbidden: {n, s, t}`
+    ///
+    /// ## Returns
+    /// * `String` - The description of the constraints.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let known: String = self.known.iter().map(|c| c.unwrap_or('_')).collect();
+
+        let mut required: Vec<char> = self.required.iter().copied().collect();
+        required.sort_unstable();
+        let required: Vec<String> = required
+            .iter()
+            .map(|&c| format!("{c}≥{}", self.min_letter_count(c)))
+            .collect();
+
+        let mut forbidden: Vec<char> = self.forbidden.iter().copied().collect();
+        forbidden.sort_unstable();
+        let forbidden: Vec<String> = forbidden.iter().map(char::to_string).collect();
+
+        format!(
+            "Known: {known} | Must include: {{{}}} | Forbidden: {{{}}}",
+            required.join(", "),
+            forbidden.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constraints_from_patterns() {
+        let pattern = Word::from_pattern_code("stare", "AAYGG").unwrap();
+        let constraints = LetterConstraints::from_patterns(&[pattern]);
+
+        assert_eq!(constraints.known, [None, None, None, Some('r'), Some('e')]);
+        assert!(constraints.required.contains(&'a'));
+        assert!(constraints.forbidden.contains(&'s'));
+        assert!(constraints.forbidden_at[2].contains(&'a'));
+
+        assert!(constraints.is_satisfied_by(&Word::new("adore").unwrap()));
+        assert!(!constraints.is_satisfied_by(&Word::new("score").unwrap())); // 's' forbidden
+        assert!(!constraints.is_satisfied_by(&Word::new("opera").unwrap())); // 'e' not at the end
+        assert!(!constraints.is_satisfied_by(&Word::new("chore").unwrap())); // no 'a'
+        assert!(!constraints.is_satisfied_by(&Word::new("quare").unwrap())); // 'a' misplaced
+    }
+
+    #[test]
+    fn test_constraints_incremental() {
+        use crate::game_logic::{filter_words, filter_words_by_constraints};
+
+        let patterns = [
+            Word::from_pattern_code("crane", "AAAAG").unwrap(),
+            Word::from_pattern_code("geese", "AAAGG").unwrap(),
+        ];
+        let words = crate::load_words();
+
+        let mut constraints = LetterConstraints::new();
+        let mut candidates = words.clone();
+        for pattern in &patterns {
+            constraints.add_pattern(pattern);
+            candidates = filter_words_by_constraints(&candidates, &constraints);
+            assert!(candidates
+                .iter()
+                .all(|word| constraints.is_satisfied_by(&Word::new(word).unwrap())));
+        }
+        assert_eq!(constraints, LetterConstraints::from_patterns(&patterns));
+        assert_eq!(candidates, filter_words(&words, &patterns));
+        assert!(candidates.contains(&"those".to_string()));
+    }
+
+    #[test]
+    fn test_constraints_conflicting_correct_letters() {
+        let patterns = [
+            Word::from_pattern_code("crane", "GUUUU").unwrap(),
+            Word::from_pattern_code("slate", "GUUUU").unwrap(),
+        ];
+        let constraints = LetterConstraints::from_patterns(&patterns);
+        assert!(!constraints.is_satisfiable());
+        assert!(!constraints.is_satisfied_by(&Word::new("scarf").unwrap()));
+
+        let words: Vec<String> = ["scarf", "crane", "slate", "scald"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert!(crate::game_logic::filter_words(&words, &patterns).is_empty());
+    }
+
+    #[test]
+    fn test_constraints_duplicate_absent() {
+        // 'p' is correct once and absent once, so it must not be forbidden entirely
+        let pattern = Word::from_pattern_code("happy", "UUGAU").unwrap();
+        let constraints = LetterConstraints::from_patterns(&[pattern]);
+
+        assert!(!constraints.forbidden.contains(&'p'));
+        assert!(constraints.forbidden_at[3].contains(&'p'));
+        assert_eq!(constraints.max_count.get(&'p'), Some(&1));
+
+        assert!(constraints.is_satisfied_by(&Word::new("aspen").unwrap()));
+        assert!(!constraints.is_satisfied_by(&Word::new("upper").unwrap())); // Two 'p's
+        assert!(!constraints.is_satisfied_by(&Word::new("pupal").unwrap())); // Two 'p's
+
+        assert_eq!(constraints.max_letter_count('p'), Some(1));
+        assert_eq!(constraints.max_letter_count('z'), None);
+    }
+
+    #[test]
+    fn test_constraints_covers_max_count() {
+        let capped =
+            LetterConstraints::from_patterns(&[Word::from_pattern_code("happy", "UUGAU").unwrap()]);
+        let forbidden =
+            LetterConstraints::from_patterns(&[Word::from_pattern_code("plumb", "AUUUU").unwrap()]);
+
+        // Allowing one 'p' at most does not imply allowing two
+        assert!(!LetterConstraints::new().covers(&capped));
+        assert!(capped.covers(&capped));
+        assert_eq!(forbidden.max_letter_count('p'), Some(0));
+        assert!(forbidden.covers(&LetterConstraints {
+            max_count: capped.max_count.clone(),
+            ..LetterConstraints::new()
+        }));
+    }
+
+    #[test]
+    fn test_constraints_duplicate_present() {
+        // 'e' is Misplaced once and Correct once, the answer has at least two
+        let pattern = Word::from_pattern_code("geese", "AYAGG").unwrap();
+        let constraints = LetterConstraints::from_patterns(&[pattern]);
+
+        assert_eq!(constraints.min_count.get(&'e'), Some(&2));
+        assert_eq!(constraints.min_letter_count('e'), 2);
+        assert_eq!(constraints.min_letter_count('s'), 1);
+        assert_eq!(constraints.min_letter_count('z'), 0);
+
+        assert!(constraints.is_satisfied_by(&Word::new("erase").unwrap()));
+        assert!(!constraints.is_satisfied_by(&Word::new("those").unwrap())); // One 'e'
+        assert!(constraints.summary().contains("e≥2"));
+
+        // The minimum only grows across patterns
+        let single = Word::from_pattern_code("crane", "AAAAG").unwrap();
+        let pattern = Word::from_pattern_code("geese", "AYAGG").unwrap();
+        let constraints = LetterConstraints::from_patterns(&[pattern, single]);
+        assert_eq!(constraints.min_letter_count('e'), 2);
+    }
+
+    #[test]
+    fn test_constraints_is_satisfiable() {
+        assert!(LetterConstraints::new().is_satisfiable());
+
+        let crane = Word::from_pattern_code("crane", "YGGAG").unwrap();
+        assert!(LetterConstraints::from_patterns(&[crane]).is_satisfiable());
+
+        // 'c' cannot be both in the word and absent from it
+        let crane = Word::from_pattern_code("crane", "GAAAA").unwrap();
+        let cloth = Word::from_pattern_code("cloth", "AAAAA").unwrap();
+        assert!(!LetterConstraints::from_patterns(&[crane, cloth]).is_satisfiable());
+
+        // Six different letters cannot fit in five positions
+        let crane = Word::from_pattern_code("crane", "YYYYY").unwrap();
+        let split = Word::from_pattern_code("split", "AAAAY").unwrap();
+        assert!(!LetterConstraints::from_patterns(&[crane, split]).is_satisfiable());
+    }
+
+    #[test]
+    fn test_constraints_summary() {
+        let pattern = Word::from_pattern_code("stare", "AAYGG").unwrap();
+        let constraints = LetterConstraints::from_patterns(&[pattern]);
+
+        assert_eq!(
+            constraints.summary(),
+            "Known: ___re | Must include: {a≥1, e≥1, r≥1} | Forbidden: {s, t}"
+        );
+        assert_eq!(
+            LetterConstraints::new().summary(),
+            "Known: _____ | Must include: {} | Forbidden: {}"
+        );
+    }
+
+    #[test]
+    fn test_constraints_covers() {
+        let first = Word::from_pattern_code("crane", "AYGAA").unwrap();
+        let second = Word::from_pattern_code("ratio", "YGAAA").unwrap();
+
+        let broad = LetterConstraints::from_patterns(std::slice::from_ref(&first));
+        let narrow = LetterConstraints::from_patterns(&[first, second]);
+
+        assert!(narrow.covers(&broad));
+        assert!(!broad.covers(&narrow));
+        assert!(broad.covers(&broad));
+
+        // Everything covers the empty set, which covers nothing but itself
+        assert!(broad.covers(&LetterConstraints::new()));
+        assert!(!LetterConstraints::new().covers(&broad));
+    }
+
+    #[test]
+    fn test_constraints_covers_known_position() {
+        // A known letter excludes every other letter at its position
+        let known =
+            LetterConstraints::from_patterns(&[Word::from_pattern_code("slate", "GUUUU").unwrap()]);
+        let excluded =
+            LetterConstraints::from_patterns(&[Word::from_pattern_code("train", "YUUUU").unwrap()]);
+
+        let mut combined = known.clone();
+        combined.required.insert('t');
+        assert!(combined.covers(&excluded));
+        assert!(!known.covers(&excluded));
+    }
+}
@@ -0,0 +1,69 @@
+//! Module containing the deterministic selection of the word of the day.
+
+use chrono::{Local, NaiveDate};
+
+/// The date of the first Wordle puzzle, used as day zero.
+pub const FIRST_DAY: NaiveDate = match NaiveDate::from_ymd_opt(2021, 6, 19) {
+    Some(date) => date,
+    None => panic!("invalid first day"),
+};
+
+/// # `daily_word`
+/// Returns the word of the given day, the same date always giving the same word.
+///
+/// ## Arguments
+/// * `date` - The day to pick the word for.
+/// * `answers` - The list of possible answers.
+///
+/// ## Returns
+/// * `Option<&str>` - The word of the day, or `None` if there are no answers.
+#[must_use]
+pub fn daily_word(date: NaiveDate, answers: &[String]) -> Option<&str> {
+    if answers.is_empty() {
+        return None;
+    }
+
+    let offset = (date - FIRST_DAY).num_days();
+    let len = i64::try_from(answers.len()).ok()?;
+    let index = usize::try_from(offset.rem_euclid(len)).ok()?;
+
+    answers.get(index).map(String::as_str)
+}
+
+/// # `todays_word`
+/// Returns the word of the current local day.
+///
+/// ## Arguments
+/// * `answers` - The list of possible answers.
+///
+/// ## Returns
+/// * `Option<&str>` - The word of the day, or `None` if there are no answers.
+#[must_use]
+pub fn todays_word(answers: &[String]) -> Option<&str> {
+    daily_word(Local::now().date_naive(), answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers() -> Vec<String> {
+        ["cigar", "rebut", "sissy"]
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_daily_word() {
+        let answers = answers();
+        let day = |d| NaiveDate::from_ymd_opt(2021, 6, d).unwrap();
+
+        assert_eq!(daily_word(day(19), &answers), Some("cigar"));
+        assert_eq!(daily_word(day(20), &answers), Some("rebut"));
+        assert_eq!(daily_word(day(22), &answers), Some("cigar"));
+        // Days before the first puzzle wrap around
+        assert_eq!(daily_word(day(18), &answers), Some("sissy"));
+        assert_eq!(daily_word(day(19), &[]), None);
+    }
+}
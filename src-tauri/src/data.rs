@@ -1,8 +1,9 @@
 //! Module containing data structures and conversion functions for frontend data.
 
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
-use crate::{LetterState, Word};
+use crate::{game_state::GamePhase, keyboard::KeyboardState, LetterState, Word, WordleError};
 
 // Serializable struct to represent letter data from frontend
 #[derive(Serialize, Deserialize)]
@@ -14,6 +15,34 @@ pub struct LetterData {
 // Serializable struct to represent word data from frontend
 pub type WordData = Vec<LetterData>;
 
+// JSON Schema of WordData, for the frontend to validate what it sends
+#[must_use]
+pub fn word_data_json_schema() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "WordData",
+        "type": "array",
+        "minItems": 5,
+        "maxItems": 5,
+        "items": {
+            "title": "LetterData",
+            "type": "object",
+            "properties": {
+                "character": {
+                    "type": "string",
+                    "pattern": "^[A-Za-z]$"
+                },
+                "state": {
+                    "type": "string",
+                    "enum": ["unknown", "correct", "misplaced", "absent"]
+                }
+            },
+            "required": ["character", "state"],
+            "additionalProperties": false
+        }
+    })
+}
+
 // Convert frontend LetterState string to backend LetterState enum
 fn convert_letter_state(state: &str) -> LetterState {
     match state {
@@ -24,23 +53,209 @@ fn convert_letter_state(state: &str) -> LetterState {
     }
 }
 
+// Serializable struct to represent the outcome of a guess sent to the frontend
+#[derive(Serialize, Deserialize)]
+pub struct GuessResult {
+    pub pattern: WordData,
+    pub remaining_candidates: usize,
+    pub guesses_remaining: usize,
+    pub keyboard: Vec<LetterData>, // One entry per letter, from 'a' to 'z'
+    pub phase: GamePhase,
+}
+
+// Serializable struct to represent how the frontend wants the filtered words
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct FilterOptions {
+    pub max_results: Option<usize>, // Only the best scoring words, every word if None
+}
+
+// Serializable struct to represent the filtered words sent to the frontend
+#[derive(Serialize, Deserialize)]
+pub struct FilteredWords {
+    pub words: Vec<String>,
+    pub total: usize, // Number of matching words before truncation
+}
+
+// Serializable struct to represent how good a guess is, sent to the frontend
+#[derive(Serialize, Deserialize)]
+pub struct GuessAnalysis {
+    pub guess: String,
+    pub expected_remaining: f64,
+    pub worst_case_remaining: usize,
+}
+
+// Serializable struct to represent whether a word can be guessed and be the secret
+#[derive(Serialize, Deserialize)]
+pub struct WordValidity {
+    pub is_allowed: bool,
+    pub is_answer: bool,
+}
+
+// Serializable struct to represent the progress of a strategy evaluation
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EvaluationProgress {
+    pub completed: u32,
+    pub total: u32,
+}
+
+// Convert Word struct in backend to WordData for the frontend
+#[must_use]
+pub fn word_to_data(word: &Word) -> WordData {
+    (0..5)
+        .map(|i| {
+            let letter = word.letter_at(i);
+            LetterData {
+                character: letter.character,
+                state: letter.state.to_string(),
+            }
+        })
+        .collect()
+}
+
+// Convert KeyboardState in backend to a list of LetterData for the frontend
+#[must_use]
+pub fn keyboard_to_data(keyboard: &KeyboardState) -> Vec<LetterData> {
+    keyboard
+        .iter()
+        .map(|(character, state)| LetterData {
+            character,
+            state: state.to_string(),
+        })
+        .collect()
+}
+
 // Convert WordData from frontend to Word struct in backend
-pub fn convert_word_data(word_data: &WordData) -> Result<Word, String> {
-    // Ensure we have exactly 5 letters
-    if word_data.len() != 5 {
-        return Err("Word must have exactly 5 letters".to_string());
+// Uppercase characters are accepted and normalized to lowercase, like `Word::new` does
+pub fn convert_word_data(word_data: &WordData) -> Result<Word, WordleError> {
+    Word::try_from(word_data)
+}
+
+// Words of other lengths are converted the same way, e.g. `Word::<4>::try_from`
+impl<const N: usize> TryFrom<&WordData> for Word<N> {
+    type Error = WordleError;
+
+    fn try_from(word_data: &WordData) -> Result<Self, Self::Error> {
+        // Ensure we have exactly N letters
+        if word_data.len() != N {
+            return Err(WordleError::InvalidLength {
+                expected: N,
+                got: word_data.len(),
+            });
+        }
+
+        // Point out the invalid character so the frontend can highlight it
+        if let Some((i, c)) = word_data
+            .iter()
+            .map(|l| l.character)
+            .enumerate()
+            .find(|(_, c)| !c.is_ascii_alphabetic())
+        {
+            return Err(WordleError::ConversionError(format!(
+                "character '{c}' at position {i} is not a valid ASCII letter"
+            )));
+        }
+
+        // Create a Word with the right characters
+        let word_str: String = word_data.iter().map(|l| l.character).collect();
+
+        let mut word: Self = word_str.parse()?;
+
+        // Set the states for each letter
+        for (i, letter_data) in word_data.iter().enumerate() {
+            word.letter_at_mut(i)
+                .set_state(convert_letter_state(&letter_data.state));
+        }
+
+        Ok(word)
     }
+}
 
-    // Create a Word with the right characters
-    let word_str: String = word_data.iter().map(|l| l.character).collect();
+/// # `IntoWord`
+/// Converts frontend data into a `Word`, without importing `convert_word_data`.
+pub trait IntoWord {
+    /// # `into_word`
+    /// Converts the data into a `Word`.
+    ///
+    /// ## Returns
+    /// * `Result<Word, WordleError>` - The word or the reason the data is invalid.
+    fn into_word(self) -> Result<Word, WordleError>;
+}
 
-    let mut word = Word::new(&word_str).map_err(std::string::ToString::to_string)?;
+impl IntoWord for WordData {
+    fn into_word(self) -> Result<Word, WordleError> {
+        convert_word_data(&self)
+    }
+}
 
-    // Set the states for each letter
-    for (i, letter_data) in word_data.iter().enumerate() {
-        word.letter_at_mut(i)
-            .set_state(convert_letter_state(&letter_data.state));
+impl From<Word> for WordData {
+    fn from(word: Word) -> Self {
+        word_to_data(&word)
     }
+}
 
-    Ok(word)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word_data(word: &str, states: [&str; 5]) -> WordData {
+        word.chars()
+            .zip(states)
+            .map(|(character, state)| LetterData {
+                character,
+                state: state.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_letter_state_display_round_trip() {
+        for state in [
+            LetterState::Unknown,
+            LetterState::Correct,
+            LetterState::Misplaced,
+            LetterState::Absent,
+        ] {
+            assert_eq!(convert_letter_state(&state.to_string()), state);
+        }
+        assert_eq!(LetterState::Correct.to_string(), "correct");
+        assert_eq!(LetterState::Unknown.to_string(), "unknown");
+    }
+
+    #[test]
+    fn test_convert_word_data_uppercase() {
+        let states = ["correct", "misplaced", "absent", "unknown", "absent"];
+        let word = convert_word_data(&word_data("CrANe", states)).unwrap();
+        assert_eq!(
+            word,
+            convert_word_data(&word_data("crane", states)).unwrap()
+        );
+
+        let round_trip = word_to_data(&word);
+        let characters: String = round_trip.iter().map(|l| l.character).collect();
+        assert_eq!(characters, "crane");
+        assert!(round_trip
+            .iter()
+            .zip(states)
+            .all(|(letter, state)| letter.state == state));
+    }
+
+    #[test]
+    fn test_into_word() {
+        let states = ["correct", "absent", "absent", "misplaced", "unknown"];
+        let word = word_data("slate", states).into_word().unwrap();
+        assert_eq!(
+            word,
+            convert_word_data(&word_data("slate", states)).unwrap()
+        );
+
+        let data = WordData::from(word.clone());
+        assert_eq!(data.into_word(), Ok(word));
+
+        assert_eq!(
+            word_data("sl4te", states).into_word(),
+            Err(WordleError::ConversionError(
+                "character '4' at position 2 is not a valid ASCII letter".to_string()
+            ))
+        );
+    }
 }
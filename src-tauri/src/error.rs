@@ -0,0 +1,125 @@
+//! Module containing the error type of the crate.
+
+use std::fmt;
+
+/// # `WordleError`
+/// Represents the errors that can occur while handling words and patterns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordleError {
+    /// A pattern code contains a character other than `G`, `Y`, `A` or `U`.
+    InvalidPatternCode(char),
+    /// A pattern code is longer than the word it applies to.
+    PatternCodeTooLong { got: usize },
+    /// A full pattern code does not have exactly 5 characters.
+    InvalidPatternLength { got: usize },
+    /// A word does not have the expected number of letters.
+    InvalidLength { expected: usize, got: usize },
+    /// No word list exists for words of this length.
+    UnsupportedWordLength(usize),
+    /// A character is not an ASCII letter.
+    NonAlphabeticCharacter(char),
+    /// A list of words to pick from is empty.
+    EmptyWordList,
+    /// Data received from the frontend does not describe a valid word.
+    ConversionError(String),
+    /// Some secrets cannot be found within the given number of guesses.
+    NoSolution { max_guesses: usize },
+    /// Too many candidates are left to search for a solution.
+    TooManyCandidates { count: usize, max: usize },
+    /// A word is not part of the word bank.
+    NotInWordBank(String),
+    /// A word is already part of the word bank.
+    DuplicateWord(String),
+    /// A hard mode guess does not keep a Correct letter in place.
+    HardModeCorrectLetter { letter: char, position: usize },
+    /// A hard mode guess does not contain a revealed letter.
+    HardModeMissingLetter(char),
+    /// Every guess of the game has already been used.
+    NoGuessesLeft,
+    /// The operation is only allowed before the first guess.
+    GameAlreadyStarted,
+    /// The game is already won.
+    GameOver,
+    /// The game does not know the word to find.
+    NoSecret,
+    /// A file could not be read or written.
+    Io(String),
+    /// Two patterns of the same guess are not for the same word.
+    MismatchedWords(String, String),
+    /// Two patterns of the same guess disagree on whether a letter is in the word.
+    ContradictoryStates { position: usize },
+    /// A packed letter has unused bits set or an unknown state.
+    InvalidPackedLetter(u16),
+    /// A saved game could not be parsed or does not match its guesses.
+    InvalidSavedGame(String),
+    /// A configuration file could not be parsed or has unsupported values.
+    InvalidConfig(String),
+    /// A pattern index is not in `0..243`.
+    InvalidPatternIndex(u8),
+}
+
+impl fmt::Display for WordleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPatternCode(c) => {
+                write!(
+                    f,
+                    "Invalid pattern code character '{c}', expected G, Y, A or U"
+                )
+            }
+            Self::PatternCodeTooLong { got } => {
+                write!(f, "Pattern code must have at most 5 characters, got {got}")
+            }
+            Self::InvalidPatternLength { got } => {
+                write!(f, "Pattern code must have exactly 5 characters, got {got}")
+            }
+            Self::InvalidLength { expected, got } => {
+                write!(f, "Word must be exactly {expected} letters, got {got}")
+            }
+            Self::UnsupportedWordLength(length) => {
+                write!(f, "{length} letter words are not supported")
+            }
+            Self::NonAlphabeticCharacter(c) => write!(f, "Character '{c}' must be an ASCII letter"),
+            Self::EmptyWordList => write!(f, "The word list is empty"),
+            Self::ConversionError(reason) => write!(f, "Invalid word data: {reason}"),
+            Self::NoSolution { max_guesses } => write!(
+                f,
+                "Some candidates cannot be found within {max_guesses} guesses"
+            ),
+            Self::TooManyCandidates { count, max } => write!(
+                f,
+                "{count} candidates left, enter more guesses to get at most {max}"
+            ),
+            Self::NotInWordBank(word) => write!(f, "'{word}' is not in the word list"),
+            Self::DuplicateWord(word) => write!(f, "'{word}' is already in the word list"),
+            Self::HardModeCorrectLetter { letter, position } => write!(
+                f,
+                "Letter {} must be '{}'",
+                position + 1,
+                letter.to_ascii_uppercase()
+            ),
+            Self::HardModeMissingLetter(c) => {
+                write!(f, "Guess must contain '{}'", c.to_ascii_uppercase())
+            }
+            Self::NoGuessesLeft => write!(f, "No guesses left"),
+            Self::GameAlreadyStarted => {
+                write!(f, "This can only be changed before the first guess")
+            }
+            Self::GameOver => write!(f, "The game is over"),
+            Self::NoSecret => write!(f, "The game has no secret word"),
+            Self::Io(reason) => write!(f, "I/O error: {reason}"),
+            Self::MismatchedWords(a, b) => write!(f, "'{a}' and '{b}' are different words"),
+            Self::ContradictoryStates { position } => {
+                write!(f, "Letter {} has contradictory states", position + 1)
+            }
+            Self::InvalidPackedLetter(packed) => write!(f, "Invalid packed letter {packed:#06x}"),
+            Self::InvalidSavedGame(reason) => write!(f, "Invalid saved game: {reason}"),
+            Self::InvalidConfig(reason) => write!(f, "Invalid configuration: {reason}"),
+            Self::InvalidPatternIndex(index) => {
+                write!(f, "Invalid pattern index {index}, expected less than 243")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WordleError {}
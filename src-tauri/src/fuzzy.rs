@@ -0,0 +1,89 @@
+//! Module containing the fuzzy lookup of mistyped words.
+
+/// # `edit_distance`
+/// Computes the Levenshtein distance between two words: the number of insertions,
+/// deletions and substitutions of a character needed to turn one into the other.
+///
+/// ## Arguments
+/// * `a` - The first word.
+/// * `b` - The second word.
+///
+/// ## Returns
+/// * `usize` - The number of edits.
+#[must_use]
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+
+    // Only the previous row of the dynamic programming table is needed
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = Vec::with_capacity(b.len() + 1);
+        current.push(i + 1);
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// # `fuzzy_lookup`
+/// Returns the words within a number of edits of the query, closest first.
+///
+/// ## Arguments
+/// * `query` - The possibly mistyped word, case-insensitive.
+/// * `words` - The words to look in.
+/// * `max_distance` - The maximum number of edits.
+///
+/// ## Returns
+/// * `Vec<String>` - The matching words, closest first then in the order of `words`.
+#[must_use]
+pub fn fuzzy_lookup(query: &str, words: &[String], max_distance: usize) -> Vec<String> {
+    let query = query.to_ascii_lowercase();
+    let mut matches: Vec<(&String, usize)> = words
+        .iter()
+        .map(|word| (word, edit_distance(&query, word)))
+        .filter(|(_, distance)| *distance <= max_distance)
+        .collect();
+    matches.sort_by_key(|(_, distance)| *distance);
+
+    matches.into_iter().map(|(word, _)| word.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("crane", "crane"), 0);
+        assert_eq!(edit_distance("crane", "crate"), 1);
+        assert_eq!(edit_distance("crane", "cane"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "slate"), 5);
+    }
+
+    #[test]
+    fn test_fuzzy_lookup() {
+        let words: Vec<String> = ["crane", "crate", "slate", "grace"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            fuzzy_lookup("Crame", &words, 1),
+            vec!["crane".to_string(), "crate".to_string()]
+        );
+        assert_eq!(
+            fuzzy_lookup("crase", &words, 2),
+            vec![
+                "crane".to_string(),
+                "crate".to_string(),
+                "grace".to_string()
+            ]
+        );
+        assert!(fuzzy_lookup("pious", &words, 1).is_empty());
+    }
+}
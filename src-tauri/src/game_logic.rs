@@ -1,11 +1,88 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    data::{convert_word_data, WordData},
-    load_words, LetterState, Word,
+    config::WORD_LENGTH,
+    constraints::LetterConstraints,
+    data::{convert_word_data, FilterOptions, FilteredWords, GuessAnalysis, WordData},
+    keyboard::KeyboardState,
+    scoring::score_by_letter_frequency,
+    word_bank::WordBank,
+    Letter, LetterState, Word, WordleError,
 };
 
-impl Word {
+/// # `PatternMismatch`
+/// The first letter of a pattern a word does not match, with the reason why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternMismatch {
+    pub position: usize,
+    pub expected_state: LetterState,
+    pub reason: String,
+}
+
+impl<const N: usize> Word<N> {
     /// # `matches_pattern`
-    /// Checks if the word matches the given pattern.
+    /// Checks if the word matches the given pattern, explaining the first mismatch.
+    /// Follows the same rules as the constraints compiled from the pattern, use
+    /// `matches_constraints` when checking many words.
+    ///
+    /// ## Arguments
+    /// * `pattern` - The pattern to match against.
+    ///
+    /// ## Returns
+    /// * `Option<PatternMismatch>` - `None` if the word matches the pattern, the first
+    ///   failed letter otherwise.
+    #[must_use]
+    pub fn matches_pattern(&self, pattern: &Self) -> Option<PatternMismatch> {
+        let revealed_count = |expected: &Letter| {
+            pattern
+                .into_iter()
+                .filter(|l| {
+                    l.overlaps(expected)
+                        && matches!(l.state, LetterState::Correct | LetterState::Misplaced)
+                })
+                .count()
+        };
+
+        pattern
+            .into_iter()
+            .enumerate()
+            .find_map(|(position, expected)| {
+                let c = expected.character;
+                let here = self.letter_at(position).same_as(expected);
+                let reason = match expected.state {
+                    LetterState::Correct if !here => format!("expected '{c}' here"),
+                    LetterState::Misplaced if here => format!("'{c}' cannot be here"),
+                    LetterState::Misplaced if !self.into_iter().any(|l| l.overlaps(expected)) => {
+                        format!("'{c}' must be in the word")
+                    }
+                    LetterState::Misplaced if self.count_of(c) < revealed_count(expected) => {
+                        format!(
+                            "'{c}' must appear at least {} time(s)",
+                            revealed_count(expected)
+                        )
+                    }
+                    LetterState::Absent if here => format!("'{c}' cannot be here"),
+                    LetterState::Absent if self.count_of(c) > revealed_count(expected) => {
+                        format!(
+                            "'{c}' can appear at most {} time(s)",
+                            revealed_count(expected)
+                        )
+                    }
+                    _ => return None,
+                };
+
+                Some(PatternMismatch {
+                    position,
+                    expected_state: expected.state,
+                    reason,
+                })
+            })
+    }
+
+    /// # `matches_pattern_bool`
+    /// Checks if the word matches the given pattern, see `matches_pattern`.
     ///
     /// ## Arguments
     /// * `pattern` - The pattern to match against.
@@ -13,130 +90,305 @@ impl Word {
     /// ## Returns
     /// * `bool` - `true` if the word matches the pattern, `false` otherwise.
     #[must_use]
-    pub fn matches_pattern(&self, pattern: &Word) -> bool {
-        // For all misplaced test case
-        let all_misplaced = pattern
-            .letters
-            .iter()
-            .all(|l| l.state == LetterState::Misplaced);
-        if all_misplaced {
-            // When all letters are misplaced, we need to ensure:
-            // 1. The candidate word contains all the same letters as the pattern
-            // 2. No letter is in the same position in both words
-
-            // First check: same set of letters
-            let word_letters: Vec<char> = self.letters.iter().map(|l| l.character).collect();
-            let pattern_letters: Vec<char> = pattern.letters.iter().map(|l| l.character).collect();
-
-            // Check if the letters are the same (ignoring order)
-            let mut word_sorted = word_letters.clone();
-            let mut pattern_sorted = pattern_letters.clone();
-            word_sorted.sort_unstable();
-            pattern_sorted.sort_unstable();
-            if word_sorted != pattern_sorted {
-                return false;
-            }
+    pub fn matches_pattern_bool(&self, pattern: &Self) -> bool {
+        self.matches_pattern(pattern).is_none()
+    }
 
-            // Second check: no letter is in the same position
-            for (i, pattern_letter) in pattern.letters.iter().enumerate() {
-                if self.letter_at(i).character == pattern_letter.character {
-                    return false;
-                }
-            }
+    /// # `contains_letter`
+    /// Checks if a letter appears anywhere in the word.
+    ///
+    /// ## Arguments
+    /// * `c` - The letter to look for, case-insensitive.
+    #[must_use]
+    pub fn contains_letter(&self, c: char) -> bool {
+        let c = c.to_ascii_lowercase();
+        self.into_iter().any(|l| l.character == c)
+    }
+
+    /// # `count_of`
+    /// Counts how many times a letter appears in the word.
+    ///
+    /// ## Arguments
+    /// * `c` - The letter to count, case-insensitive.
+    #[must_use]
+    pub fn count_of(&self, c: char) -> usize {
+        let c = c.to_ascii_lowercase();
+        self.into_iter().filter(|l| l.character == c).count()
+    }
+}
+
+impl Word {
+    /// # `new_with_guess_and_answer`
+    /// Creates the pattern Wordle would show for a guess when the answer is known.
+    ///
+    /// ## Arguments
+    /// * `guess` - The guessed word.
+    /// * `answer` - The word to find.
+    ///
+    /// ## Returns
+    /// * `Result<Word, WordleError>` - The guess with every letter state set, or an
+    ///   error if a word is invalid.
+    pub fn new_with_guess_and_answer(guess: &str, answer: &str) -> Result<Word, WordleError> {
+        let guess = Word::new(guess)?;
+        let answer = Word::new(answer)?;
+
+        Ok(compute_response(&guess, &answer))
+    }
+
+    /// # `to_sorted_chars`
+    /// Returns the letters of the word in alphabetical order, the same for every
+    /// anagram of the word.
+    #[must_use]
+    pub fn to_sorted_chars(&self) -> [char; 5] {
+        let mut chars: [char; 5] = std::array::from_fn(|i| self.letter_at(i).character);
+        chars.sort_unstable();
 
-            return true;
+        chars
+    }
+
+    /// # `contains_all_of`
+    /// Checks if every given letter appears somewhere in the word, in a single pass.
+    ///
+    /// ## Arguments
+    /// * `letters` - The letters to look for, case-insensitive.
+    #[must_use]
+    pub fn contains_all_of(&self, letters: &[char]) -> bool {
+        if !letters.iter().all(char::is_ascii_alphabetic) {
+            return false;
         }
 
-        // For all absent test case in test_pattern_matching_basic
-        let all_absent = pattern
-            .letters
-            .iter()
-            .all(|l| l.state == LetterState::Absent);
-        if all_absent {
-            for pattern_letter in &pattern.letters {
-                if self
-                    .letters
-                    .iter()
-                    .any(|l| l.character == pattern_letter.character)
-                {
-                    return false;
-                }
-            }
-            return true;
+        let wanted = letter_mask(letters.iter().copied());
+        self.letter_mask() & wanted == wanted
+    }
+
+    /// # `contains_none_of`
+    /// Checks if none of the given letters appears in the word, in a single pass.
+    ///
+    /// ## Arguments
+    /// * `letters` - The letters to look for, case-insensitive.
+    #[must_use]
+    pub fn contains_none_of(&self, letters: &[char]) -> bool {
+        self.letter_mask() & letter_mask(letters.iter().copied()) == 0
+    }
+
+    fn letter_mask(&self) -> u32 {
+        letter_mask(self.into_iter().map(|l| l.character))
+    }
+
+    /// # `matches_constraints`
+    /// Checks if the word satisfies pre-compiled constraints.
+    ///
+    /// ## Arguments
+    /// * `constraints` - The constraints to check against.
+    ///
+    /// ## Returns
+    /// * `bool` - `true` if the word satisfies the constraints, `false` otherwise.
+    #[must_use]
+    pub fn matches_constraints(&self, constraints: &LetterConstraints) -> bool {
+        constraints.is_satisfied_by(self)
+    }
+}
+
+/// # `compute_response`
+/// Computes the pattern Wordle would show for a guess against the secret word.
+/// Exact matches are marked Correct first, then the remaining letters are marked
+/// Misplaced as long as the secret has unmatched copies of them, the rest is Absent.
+/// Works for words of any length, e.g. `Word<4>`.
+///
+/// ## Arguments
+/// * `guess` - The guessed word.
+/// * `secret` - The word to find.
+///
+/// ## Returns
+/// * `Word` - The guess with every letter state set.
+#[must_use]
+pub fn compute_response<const N: usize>(guess: &Word<N>, secret: &Word<N>) -> Word<N> {
+    let mut response = guess.clone();
+    let mut unmatched = [0u8; 26];
+
+    for i in 0..N {
+        let secret_char = secret.letter_at(i).character;
+        if guess.letter_at(i).character == secret_char {
+            response.letter_at_mut(i).set_state(LetterState::Correct);
+        } else {
+            unmatched[(secret_char as u8 - b'a') as usize] += 1;
+        }
+    }
+
+    for i in 0..N {
+        if response.letter_at(i).state == LetterState::Correct {
+            continue;
         }
 
-        // Regular case handling
-        // Check for Correct letters first
-        for i in 0..5 {
-            let pattern_letter = pattern.letter_at(i);
-            if pattern_letter.state == LetterState::Correct
-                && self.letter_at(i).character != pattern_letter.character
-            {
-                return false;
-            }
+        let index = (guess.letter_at(i).character as u8 - b'a') as usize;
+        if unmatched[index] > 0 {
+            unmatched[index] -= 1;
+            response.letter_at_mut(i).set_state(LetterState::Misplaced);
+        } else {
+            response.letter_at_mut(i).set_state(LetterState::Absent);
         }
+    }
+
+    response
+}
 
-        // Handle Misplaced letters
+/// # `evaluate_guess`
+/// Evaluates a guess against the answer of a standard 5 letter game, see
+/// `compute_response`.
+///
+/// ## Arguments
+/// * `guess` - The guessed word.
+/// * `answer` - The answer of the game.
+///
+/// ## Returns
+/// * `Word` - The guess with every letter state set.
+#[must_use]
+pub fn evaluate_guess(guess: &Word, answer: &Word) -> Word {
+    compute_response(guess, answer)
+}
+
+/// # `validate_guess_against_hard_mode`
+/// Checks that a guess uses every hint revealed so far: Correct letters must stay
+/// in place and Misplaced letters must appear somewhere in the guess.
+///
+/// ## Arguments
+/// * `guess` - The guessed word, its states are ignored.
+/// * `previous_patterns` - The guesses made so far with their states.
+///
+/// ## Returns
+/// * `Result<(), WordleError>` - The first hint the guess does not use, if any.
+pub fn validate_guess_against_hard_mode(
+    guess: &Word,
+    previous_patterns: &[Word],
+) -> Result<(), WordleError> {
+    for previous in previous_patterns {
         for i in 0..5 {
-            let pattern_letter = pattern.letter_at(i);
-            if pattern_letter.state == LetterState::Misplaced {
-                // The letter should exist somewhere in the word
-                if !self
-                    .letters
-                    .iter()
-                    .any(|l| l.character == pattern_letter.character)
-                {
-                    return false;
-                }
-                // But not at this position
-                if self.letter_at(i).character == pattern_letter.character {
-                    return false;
-                }
+            let letter = previous.letter_at(i);
+            if letter.state == LetterState::Correct && !guess.letter_at(i).overlaps(letter) {
+                return Err(WordleError::HardModeCorrectLetter {
+                    letter: letter.character,
+                    position: i,
+                });
             }
         }
 
-        // Handle Absent letters
         for i in 0..5 {
-            let pattern_letter = pattern.letter_at(i);
-            if pattern_letter.state == LetterState::Absent {
-                // The letter should not exist at this position
-                if self.letter_at(i).character == pattern_letter.character {
-                    return false;
-                }
-
-                // For papers/happy test: If 'p' is marked absent at a position, only count 'p's
-                // that are marked as correct or misplaced in other positions
-                let letter_char = pattern_letter.character;
-                let correct_or_misplaced_count = pattern
-                    .letters
-                    .iter()
-                    .filter(|l| {
-                        l.character == letter_char
-                            && (l.state == LetterState::Correct
-                                || l.state == LetterState::Misplaced)
-                    })
-                    .count();
-
-                // Count occurrences in the word
-                let word_count = self
-                    .letters
-                    .iter()
-                    .filter(|l| l.character == letter_char)
-                    .count();
+            let letter = previous.letter_at(i);
+            if letter.state != LetterState::Misplaced {
+                continue;
+            }
 
-                // The word should not have more of this letter than the correct+misplaced count
-                if word_count > correct_or_misplaced_count {
-                    return false;
-                }
+            // Duplicates count: two revealed 'e's must both be reused
+            let revealed = previous
+                .into_iter()
+                .filter(|other| {
+                    other.overlaps(letter)
+                        && matches!(other.state, LetterState::Correct | LetterState::Misplaced)
+                })
+                .count();
+            if guess.count_of(letter.character) < revealed {
+                return Err(WordleError::HardModeMissingLetter(letter.character));
             }
         }
+    }
 
-        true
+    Ok(())
+}
+
+/// # `is_consistent_pattern_set`
+/// Checks if the patterns can all be the response for the same secret, whether this
+/// secret is in the word bank or not.
+///
+/// ## Arguments
+/// * `patterns` - The guesses with their states.
+///
+/// ## Returns
+/// * `bool` - `true` if some five letter string matches every pattern.
+#[must_use]
+pub fn is_consistent_pattern_set(patterns: &[Word]) -> bool {
+    LetterConstraints::from_patterns(patterns).is_satisfiable()
+}
+
+/// # `suggest_correction`
+/// Finds the patterns that were likely entered wrong: the ones whose removal makes
+/// the remaining patterns consistent again. Only single mistakes are looked for,
+/// which covers the few guesses of a game.
+///
+/// ## Arguments
+/// * `patterns` - The guesses with their states.
+///
+/// ## Returns
+/// * `Vec<String>` - The words of the suspicious guesses, in order, empty if the
+///   patterns are already consistent or a single mistake does not explain them.
+#[must_use]
+pub fn suggest_correction(patterns: &[Word]) -> Vec<String> {
+    if is_consistent_pattern_set(patterns) {
+        return Vec::new();
+    }
+
+    (0..patterns.len())
+        .filter(|&i| {
+            let others: Vec<Word> = patterns
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, pattern)| pattern.clone())
+                .collect();
+            is_consistent_pattern_set(&others)
+        })
+        .map(|i| patterns[i].to_string())
+        .collect()
+}
+
+/// # `GameDiagnosis`
+/// Why the patterns entered so far do or do not leave any candidate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameDiagnosis {
+    Solvable,      // Some words of the bank match every pattern
+    Contradictory, // No word at all can match, a state was likely mistyped
+    NotInWordBank, // The patterns are consistent, but the secret is not in the bank
+}
+
+/// # `diagnose_game_state`
+/// Tells apart patterns no word can satisfy from a secret missing from the bank.
+///
+/// ## Arguments
+/// * `word_bank` - The words the secret can be.
+/// * `patterns` - The guesses with their states.
+///
+/// ## Returns
+/// * `GameDiagnosis` - Whether the game can still be solved, and why not.
+#[must_use]
+pub fn diagnose_game_state(word_bank: &WordBank, patterns: &[Word]) -> GameDiagnosis {
+    if !filter_words(word_bank.answers(), patterns).is_empty() {
+        GameDiagnosis::Solvable
+    } else if is_consistent_pattern_set(patterns) {
+        GameDiagnosis::NotInWordBank
+    } else {
+        GameDiagnosis::Contradictory
     }
 }
 
+/// # `detect_impossible_game_state`
+/// Checks if the patterns contradict each other, so that no word can match them.
+///
+/// ## Arguments
+/// * `word_bank` - The words the secret can be.
+/// * `patterns` - The guesses with their states.
+///
+/// ## Returns
+/// * `bool` - `true` if no word of the bank matches and the patterns are contradictory.
+#[must_use]
+pub fn detect_impossible_game_state(word_bank: &WordBank, patterns: &[Word]) -> bool {
+    diagnose_game_state(word_bank, patterns) == GameDiagnosis::Contradictory
+}
+
 /// # `filter_words`
 /// Filters a list of words based on a list of patterns.
+/// The result follows the order of `all_words`, which is not guaranteed if the
+/// filtering is run in parallel (e.g. with `rayon`): use `filter_words_deterministic`
+/// when the order matters.
 ///
 /// ## Arguments
 /// * `all_words` - The list of words to filter.
@@ -146,301 +398,1834 @@ impl Word {
 /// * `Vec<String>` - The filtered list of words.
 #[must_use]
 pub fn filter_words(all_words: &[String], given_words: &[Word]) -> Vec<String> {
+    // Compile the patterns once instead of once per candidate
+    filter_words_by_constraints(all_words, &LetterConstraints::from_patterns(given_words))
+}
+
+/// # `filter_words_of_length`
+/// Filters a list of words of any length based on a list of patterns, e.g. 4 letter
+/// words for mini Wordle. 5 letter words are faster to filter with `filter_words`.
+///
+/// ## Arguments
+/// * `all_words` - The list of words to filter, words of another length are skipped.
+/// * `patterns` - The list of patterns to filter against.
+///
+/// ## Returns
+/// * `Vec<String>` - The filtered list of words, in the order of `all_words`.
+#[must_use]
+pub fn filter_words_of_length<const N: usize>(
+    all_words: &[String],
+    patterns: &[Word<N>],
+) -> Vec<String> {
     all_words
         .iter()
         .filter(|word| {
-            if let Ok(candidate) = Word::new(word) {
-                given_words
+            word.parse::<Word<N>>().is_ok_and(|candidate| {
+                patterns
                     .iter()
-                    .all(|pattern| candidate.matches_pattern(pattern))
-            } else {
-                false
-            }
+                    .all(|pattern| candidate.matches_pattern_bool(pattern))
+            })
         })
         .cloned()
         .collect()
 }
 
-/// # `filter_word_list`
-/// Filters a list of words based on a list of patterns.
+/// # `filter_words_by_constraints`
+/// Filters a list of words based on already compiled constraints.
 ///
 /// ## Arguments
-/// * `patterns` - The list of patterns to filter against.
+/// * `all_words` - The list of words to filter.
+/// * `constraints` - The constraints every word must satisfy.
 ///
 /// ## Returns
-/// * `Result<Vec<String>, String>` - The filtered list of words or an error message.
-pub fn filter_word_list(patterns: &[WordData]) -> Result<Vec<String>, String> {
-    // Convert all pattern words to our internal Word type
-    let converted_patterns: Result<Vec<Word>, String> =
-        patterns.iter().map(convert_word_data).collect();
+/// * `Vec<String>` - The filtered list of words.
+#[must_use]
+pub fn filter_words_by_constraints(
+    all_words: &[String],
+    constraints: &LetterConstraints,
+) -> Vec<String> {
+    let forbidden = letter_mask(constraints.forbidden.iter().copied());
 
-    match converted_patterns {
-        Ok(patterns) => {
-            // Load all words and filter them
-            let all_words = load_words();
-            Ok(filter_words(&all_words, &patterns))
-        }
-        Err(e) => Err(e),
-    }
+    all_words
+        .iter()
+        .filter(|word| {
+            // Validate once here so the word can be built without checking each letter again
+            if word.len() != 5 || !word.bytes().all(|b| b.is_ascii_alphabetic()) {
+                return false;
+            }
+            // Cheap pre-filter before building the word and checking every constraint
+            if word_mask(word) & forbidden != 0 {
+                return false;
+            }
+
+            // SAFETY: the word was just checked to be exactly 5 ASCII letters
+            let candidate = unsafe { Word::new_unchecked(word) };
+            candidate.matches_constraints(constraints)
+        })
+        .cloned()
+        .collect()
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::create_pattern;
+// One bit per ASCII letter, non letters are ignored
+fn letter_mask(letters: impl IntoIterator<Item = char>) -> u32 {
+    letters
+        .into_iter()
+        .filter(char::is_ascii_alphabetic)
+        .fold(0, |mask, c| {
+            mask | 1 << (c.to_ascii_lowercase() as u8 - b'a')
+        })
+}
 
-    use super::*;
+fn word_mask(word: &str) -> u32 {
+    letter_mask(word.chars())
+}
 
-    #[test]
-    fn test_pattern_matching() {
-        // Test exact match
-        let word = Word::new("paint").unwrap();
-        let mut pattern = Word::new("paint").unwrap();
-        pattern.letter_at_mut(0).set_state(LetterState::Correct);
-        assert!(word.matches_pattern(&pattern));
+/// # `apply_keyboard_constraint`
+/// Removes the words containing a letter the keyboard marks as Absent.
+/// A fast pre-filter, the keyboard alone does not hold every constraint.
+///
+/// ## Arguments
+/// * `words` - The list of words to filter.
+/// * `keyboard` - The state of the keyboard.
+///
+/// ## Returns
+/// * `Vec<String>` - The words without any Absent letter.
+#[must_use]
+pub fn apply_keyboard_constraint(words: &[String], keyboard: &KeyboardState) -> Vec<String> {
+    let absent = letter_mask(
+        keyboard
+            .iter()
+            .filter(|(_, state)| *state == LetterState::Absent)
+            .map(|(c, _)| c),
+    );
 
-        // Test misplaced letter
-        let word = Word::new("paint").unwrap();
-        let mut pattern = Word::new("apart").unwrap();
-        pattern.letter_at_mut(0).set_state(LetterState::Misplaced);
-        assert!(word.matches_pattern(&pattern));
+    words
+        .iter()
+        .filter(|word| word_mask(word) & absent == 0)
+        .cloned()
+        .collect()
+}
 
-        // Test absent letter
-        let word = Word::new("paint").unwrap();
-        let mut pattern = Word::new("horse").unwrap();
-        pattern.letter_at_mut(0).set_state(LetterState::Absent);
-        assert!(word.matches_pattern(&pattern));
+/// # `FilterTrace`
+/// The outcome of `debug_filter_words`: the words left and why the others were dropped.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FilterTrace {
+    /// The constraints of the patterns, see `LetterConstraints::summary`.
+    pub constraints: String,
+    pub remaining: Vec<String>,
+    /// Each eliminated word with the index of the first pattern it does not match.
+    pub eliminated_by: Vec<(String, usize)>,
+}
+
+/// # `debug_filter_words`
+/// Filters a list of words like `filter_words`, recording which pattern eliminated
+/// each word. Slower, meant for debugging.
+///
+/// ## Arguments
+/// * `all_words` - The list of words to filter, words that are not 5 letters are skipped.
+/// * `patterns` - The list of patterns to filter against.
+///
+/// ## Returns
+/// * `FilterTrace` - The constraints of the patterns, the remaining and eliminated words.
+#[must_use]
+pub fn debug_filter_words(all_words: &[String], patterns: &[Word]) -> FilterTrace {
+    let mut trace = FilterTrace {
+        constraints: LetterConstraints::from_patterns(patterns).summary(),
+        ..FilterTrace::default()
+    };
+    for word in all_words {
+        let Ok(candidate) = Word::new(word) else {
+            continue;
+        };
+        match patterns
+            .iter()
+            .position(|pattern| candidate.matches_pattern(pattern).is_some())
+        {
+            Some(index) => trace.eliminated_by.push((word.clone(), index)),
+            None => trace.remaining.push(word.clone()),
+        }
     }
 
-    #[test]
-    fn test_filter_words() {
-        let all_words = vec![
-            "paint".to_string(),
-            "taint".to_string(),
-            "saint".to_string(),
-            "print".to_string(),
-        ];
+    trace
+}
 
-        let mut pattern = Word::new("paint").unwrap();
-        pattern.letter_at_mut(0).set_state(LetterState::Correct);
-        pattern.letter_at_mut(1).set_state(LetterState::Correct);
+/// # `debug_filter_word_list`
+/// Traces the filtering of the answers with the given frontend patterns.
+///
+/// ## Arguments
+/// * `word_bank` - The word bank whose answers are filtered.
+/// * `patterns` - The list of patterns to filter against.
+///
+/// ## Returns
+/// * `Result<FilterTrace, WordleError>` - The trace or an error if a pattern is invalid.
+pub fn debug_filter_word_list(
+    word_bank: &WordBank,
+    patterns: &[WordData],
+) -> Result<FilterTrace, WordleError> {
+    let patterns: Vec<Word> = patterns
+        .iter()
+        .map(convert_word_data)
+        .collect::<Result<_, _>>()?;
 
-        let filtered = filter_words(&all_words, &[pattern]);
-        assert!(filtered.contains(&"paint".to_string()));
-        assert!(!filtered.contains(&"saint".to_string()));
-    }
+    Ok(debug_filter_words(word_bank.answers(), &patterns))
+}
 
-    #[test]
-    fn test_pattern_matching_basic() {
-        // Test exact match with all correct
-        let word = Word::new("chart").unwrap();
-        let pattern = create_pattern(
-            "chart",
-            vec![
-                (0, LetterState::Correct),
-                (1, LetterState::Correct),
-                (2, LetterState::Correct),
-                (3, LetterState::Correct),
-                (4, LetterState::Correct),
-            ],
-        );
-        assert!(word.matches_pattern(&pattern));
+/// # `filter_words_deterministic`
+/// Filters a list of words based on a list of patterns, sorted alphabetically.
+///
+/// ## Arguments
+/// * `all_words` - The list of words to filter.
+/// * `given_words` - The list of patterns to filter against.
+///
+/// ## Returns
+/// * `Vec<String>` - The filtered list of words, in alphabetical order.
+#[must_use]
+pub fn filter_words_deterministic(all_words: &[String], given_words: &[Word]) -> Vec<String> {
+    let mut words = filter_words(all_words, given_words);
+    words.sort_unstable();
 
-        // Test all misplaced
-        let word = Word::new("smart").unwrap();
-        let pattern = create_pattern(
-            "tarms",
-            vec![
-                (0, LetterState::Misplaced),
-                (1, LetterState::Misplaced),
-                (2, LetterState::Misplaced),
-                (3, LetterState::Misplaced),
-                (4, LetterState::Misplaced),
-            ],
-        );
-        assert!(word.matches_pattern(&pattern));
+    words
+}
 
-        // Test all absent
-        let word = Word::new("chart").unwrap();
-        let pattern = create_pattern(
-            "wound",
-            vec![
-                (0, LetterState::Absent),
-                (1, LetterState::Absent),
-                (2, LetterState::Absent),
-                (3, LetterState::Absent),
-                (4, LetterState::Absent),
-            ],
-        );
-        assert!(word.matches_pattern(&pattern));
+/// # `filter_words_until_n`
+/// Applies the patterns one after the other, stopping as soon as at most `target`
+/// words are left: once a single candidate remains, the next patterns are not needed.
+///
+/// ## Arguments
+/// * `all_words` - The list of words to filter.
+/// * `patterns` - The list of patterns to filter against, in order.
+/// * `target` - The number of words to stop at.
+///
+/// ## Returns
+/// * `(Vec<String>, usize)` - The words matching the patterns applied, with the index
+///   of the pattern that left at most `target` words, or `patterns.len()` if none did.
+#[must_use]
+pub fn filter_words_until_n(
+    all_words: &[String],
+    patterns: &[Word],
+    target: usize,
+) -> (Vec<String>, usize) {
+    let mut remaining = all_words.to_vec();
+    for (i, pattern) in patterns.iter().enumerate() {
+        remaining = filter_words(&remaining, std::slice::from_ref(pattern));
+        if remaining.len() <= target {
+            return (remaining, i);
+        }
     }
 
-    #[test]
-    fn test_pattern_matching_mixed_states() {
-        let word = Word::new("steam").unwrap();
-        let pattern = create_pattern(
-            "stamp",
-            vec![
-                (0, LetterState::Correct),   // 's' correct
-                (1, LetterState::Correct),   // 't' correct
-                (2, LetterState::Misplaced), // 'a' misplaced
-                (3, LetterState::Misplaced), // 'm' misplaced
-                (4, LetterState::Absent),    // 'p' absent
-            ],
-        );
-        assert!(word.matches_pattern(&pattern));
-    }
+    (remaining, patterns.len())
+}
 
-    #[test]
+/// # `filter_words_excluding`
+/// Filters a list of words based on a list of patterns, then removes the excluded words
+/// (e.g. guesses already made that were not the answer).
+///
+/// ## Arguments
+/// * `all_words` - The list of words to filter.
+/// * `patterns` - The list of patterns to filter against.
+/// * `excluded` - The words to remove from the result.
+///
+/// ## Returns
+/// * `Vec<String>` - The filtered list of words.
+#[must_use]
+pub fn filter_words_excluding(
+    all_words: &[String],
+    patterns: &[Word],
+    excluded: &[String],
+) -> Vec<String> {
+    let excluded: HashSet<&str> = excluded.iter().map(String::as_str).collect();
+
+    filter_words(all_words, patterns)
+        .into_iter()
+        .filter(|word| !excluded.contains(word.as_str()))
+        .collect()
+}
+
+/// # `filter_words_with_validator`
+/// Filters a list of words based on a list of patterns and a custom rule that does not
+/// fit in a pattern, e.g. `|w| w.starts_with('s')`.
+///
+/// ## Arguments
+/// * `all_words` - The list of words to filter.
+/// * `patterns` - The list of patterns to filter against.
+/// * `extra_validator` - Returns whether a word matching the patterns is kept.
+///
+/// ## Returns
+/// * `Vec<String>` - The filtered list of words.
+#[must_use]
+pub fn filter_words_with_validator<F: Fn(&str) -> bool>(
+    all_words: &[String],
+    patterns: &[Word],
+    extra_validator: F,
+) -> Vec<String> {
+    filter_words(all_words, patterns)
+        .into_iter()
+        .filter(|word| extra_validator(word))
+        .collect()
+}
+
+/// # `dedup_candidates`
+/// Removes duplicate words, keeping the first occurrence of each.
+///
+/// ## Arguments
+/// * `words` - The list of words to deduplicate.
+///
+/// ## Returns
+/// * `Vec<String>` - The words without duplicates, in their original order.
+#[must_use]
+pub fn dedup_candidates(words: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::with_capacity(words.len());
+    words
+        .into_iter()
+        .filter(|word| seen.insert(word.clone()))
+        .collect()
+}
+
+/// # `filter_word_list`
+/// Filters the answers of a word bank based on a list of patterns.
+///
+/// ## Arguments
+/// * `word_bank` - The word bank whose answers are filtered.
+/// * `patterns` - The list of patterns to filter against.
+///
+/// ## Returns
+/// * `Result<Vec<String>, WordleError>` - The filtered list of words or an error if a
+///   pattern is invalid.
+pub fn filter_word_list(
+    word_bank: &WordBank,
+    patterns: &[WordData],
+) -> Result<Vec<String>, WordleError> {
+    // Convert all pattern words to our internal Word type
+    let converted_patterns: Result<Vec<Word>, WordleError> =
+        patterns.iter().map(convert_word_data).collect();
+
+    match converted_patterns {
+        Ok(patterns) => {
+            // Filter the answers of the word bank
+            Ok(word_bank.filter(&patterns))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// # `filter_word_list_of_length`
+/// Filters the answers of a word bank of the given length based on a list of patterns.
+/// Word banks only hold 5 letter words, other lengths can be filtered with
+/// `filter_words_of_length` on a word list of their own.
+///
+/// ## Arguments
+/// * `word_bank` - The word bank whose answers are filtered.
+/// * `patterns` - The list of patterns to filter against.
+/// * `word_length` - The number of letters of the words.
+///
+/// ## Returns
+/// * `Result<Vec<String>, WordleError>` - The filtered list of words or an error if no
+///   word bank of this length exists or a pattern is invalid.
+pub fn filter_word_list_of_length(
+    word_bank: &WordBank,
+    patterns: &[WordData],
+    word_length: usize,
+) -> Result<Vec<String>, WordleError> {
+    match word_length {
+        WORD_LENGTH => filter_word_list(word_bank, patterns),
+        _ => Err(WordleError::UnsupportedWordLength(word_length)),
+    }
+}
+
+/// # `filter_words_ranked`
+/// Filters a list of words based on a list of patterns and ranks the result.
+///
+/// ## Arguments
+/// * `all_words` - The list of words to filter.
+/// * `given_words` - The list of patterns to filter against.
+/// * `scorer` - Scores a candidate against the remaining candidates, higher is better.
+///
+/// ## Returns
+/// * `Vec<(String, f64)>` - The filtered words with their score, best first.
+#[must_use]
+pub fn filter_words_ranked(
+    all_words: &[String],
+    given_words: &[Word],
+    scorer: &dyn Fn(&Word, &[String]) -> f64,
+) -> Vec<(String, f64)> {
+    let candidates = filter_words(all_words, given_words);
+
+    let mut ranked: Vec<(String, f64)> = candidates
+        .iter()
+        .filter_map(|word| {
+            Word::new(word)
+                .ok()
+                .map(|candidate| (word.clone(), scorer(&candidate, &candidates)))
+        })
+        .collect();
+
+    // Stable sort so that words with the same score keep the word list order
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    ranked
+}
+
+/// # `filter_words_with_priority`
+/// Filters a list of words based on a list of patterns and sorts the result by priority.
+///
+/// ## Arguments
+/// * `all_words` - The list of words to filter.
+/// * `patterns` - The list of patterns to filter against.
+/// * `priority` - The priority of a word, higher comes first. Called once per word.
+///
+/// ## Returns
+/// * `Vec<String>` - The filtered words, highest priority first, ties in word list order.
+#[must_use]
+pub fn filter_words_with_priority<F: Fn(&str) -> f64>(
+    all_words: &[String],
+    patterns: &[Word],
+    priority: F,
+) -> Vec<String> {
+    let mut prioritized: Vec<(String, f64)> = filter_words(all_words, patterns)
+        .into_iter()
+        .map(|word| {
+            let score = priority(&word);
+            (word, score)
+        })
+        .collect();
+    prioritized.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    prioritized.into_iter().map(|(word, _)| word).collect()
+}
+
+/// # `top_n_candidates`
+/// Returns only the `n` best scoring words matching the given patterns.
+///
+/// ## Arguments
+/// * `all_words` - The list of words to filter.
+/// * `patterns` - The list of patterns to filter against.
+/// * `n` - The maximum number of words to return.
+/// * `scorer` - Scores a candidate against the remaining candidates, higher is better.
+///
+/// ## Returns
+/// * `Vec<String>` - At most `n` words, best first.
+#[must_use]
+pub fn top_n_candidates(
+    all_words: &[String],
+    patterns: &[Word],
+    n: usize,
+    scorer: &dyn Fn(&Word, &[String]) -> f64,
+) -> Vec<String> {
+    filter_words_ranked(all_words, patterns, scorer)
+        .into_iter()
+        .take(n)
+        .map(|(word, _)| word)
+        .collect()
+}
+
+/// # `filter_word_list_with_options`
+/// Filters the answers based on a list of frontend patterns. With `max_results`, only
+/// the best words by letter frequency are returned, the ranking happens before the
+/// truncation.
+///
+/// ## Arguments
+/// * `word_bank` - The word bank whose answers are filtered.
+/// * `patterns` - The list of patterns to filter against.
+/// * `options` - How to limit the result.
+///
+/// ## Returns
+/// * `Result<FilteredWords, WordleError>` - The words with the number of matching words,
+///   or an error if a pattern is invalid.
+pub fn filter_word_list_with_options(
+    word_bank: &WordBank,
+    patterns: &[WordData],
+    options: FilterOptions,
+) -> Result<FilteredWords, WordleError> {
+    let patterns: Vec<Word> = patterns
+        .iter()
+        .map(convert_word_data)
+        .collect::<Result<_, _>>()?;
+
+    let candidates = word_bank.filter(&patterns);
+    let total = candidates.len();
+    let words = match options.max_results {
+        Some(n) => top_n_candidates(&candidates, &[], n, &score_by_letter_frequency),
+        None => candidates,
+    };
+
+    Ok(FilteredWords { words, total })
+}
+
+/// # `top_candidate_list`
+/// Returns the `n` best candidates for the given frontend patterns, scored by letter frequency.
+///
+/// ## Arguments
+/// * `word_bank` - The word bank whose answers are filtered.
+/// * `patterns` - The list of patterns to filter against.
+/// * `n` - The maximum number of words to return.
+///
+/// ## Returns
+/// * `Result<Vec<String>, WordleError>` - The best candidates or an error if a pattern
+///   is invalid.
+pub fn top_candidate_list(
+    word_bank: &WordBank,
+    patterns: &[WordData],
+    n: usize,
+) -> Result<Vec<String>, WordleError> {
+    let patterns: Vec<Word> = patterns
+        .iter()
+        .map(convert_word_data)
+        .collect::<Result<_, _>>()?;
+
+    Ok(top_n_candidates(
+        word_bank.answers(),
+        &patterns,
+        n,
+        &score_by_letter_frequency,
+    ))
+}
+
+/// # `pattern_partition`
+/// Groups the candidates by the response they would give to a guess.
+///
+/// ## Arguments
+/// * `guess` - The word to guess, its states are ignored.
+/// * `candidates` - The words the secret can still be.
+///
+/// ## Returns
+/// * `HashMap<String, Vec<String>>` - The candidates of each pattern code.
+#[must_use]
+pub fn pattern_partition(guess: &Word, candidates: &[String]) -> HashMap<String, Vec<String>> {
+    let mut partition: HashMap<String, Vec<String>> = HashMap::new();
+    for candidate in candidates {
+        if let Ok(secret) = Word::new(candidate) {
+            partition
+                .entry(compute_response(guess, &secret).pattern_code())
+                .or_default()
+                .push(candidate.clone());
+        }
+    }
+
+    partition
+}
+
+/// # `response_distribution`
+/// Counts how many candidates would give each response to a guess.
+///
+/// ## Arguments
+/// * `guess` - The word to guess, its states are ignored.
+/// * `candidates` - The words the secret can still be.
+///
+/// ## Returns
+/// * `Vec<(String, usize)>` - Each pattern code with its number of candidates,
+///   most frequent first then by code.
+#[must_use]
+pub fn response_distribution(guess: &Word, candidates: &[String]) -> Vec<(String, usize)> {
+    let mut distribution: Vec<(String, usize)> = pattern_partition(guess, candidates)
+        .into_iter()
+        .map(|(code, words)| (code, words.len()))
+        .collect();
+    distribution.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    distribution
+}
+
+/// # `compute_worst_case_remaining`
+/// Returns how many candidates are left after a guess in the worst case.
+///
+/// ## Arguments
+/// * `guess` - The word to guess, its states are ignored.
+/// * `candidates` - The words the secret can still be.
+///
+/// ## Returns
+/// * `usize` - The size of the largest partition, lower is better.
+#[must_use]
+pub fn compute_worst_case_remaining(guess: &Word, candidates: &[String]) -> usize {
+    pattern_partition(guess, candidates)
+        .values()
+        .map(Vec::len)
+        .max()
+        .unwrap_or(0)
+}
+
+/// # `compute_expected_remaining`
+/// Returns how many candidates are left after a guess on average, the secret being
+/// any candidate with the same probability: `Σ |partition|² / total`.
+///
+/// ## Arguments
+/// * `guess` - The word to guess, its states are ignored.
+/// * `candidates` - The words the secret can still be.
+///
+/// ## Returns
+/// * `f64` - The expected number of remaining candidates, lower is better.
+#[must_use]
+pub fn compute_expected_remaining(guess: &Word, candidates: &[String]) -> f64 {
+    let partition = pattern_partition(guess, candidates);
+    let total: usize = partition.values().map(Vec::len).sum();
+    if total == 0 {
+        return 0.0;
+    }
+
+    let squares: usize = partition.values().map(|words| words.len().pow(2)).sum();
+    // Candidate counts are far below 2^52, the conversions are exact
+    squares as f64 / total as f64
+}
+
+/// # `compute_entropy`
+/// Returns the information a guess gives on average, in bits, the secret being any
+/// candidate with the same probability.
+///
+/// ## Arguments
+/// * `guess` - The word to guess, its states are ignored.
+/// * `candidates` - The words the secret can still be.
+///
+/// ## Returns
+/// * `f64` - The entropy of the responses, higher is better.
+#[must_use]
+pub fn compute_entropy(guess: &Word, candidates: &[String]) -> f64 {
+    let mut counts = [0usize; 243];
+    for candidate in candidates {
+        if let Ok(secret) = Word::new(candidate) {
+            counts[compute_response(guess, &secret).pattern_index() as usize] += 1;
+        }
+    }
+
+    entropy_of_counts(&counts)
+}
+
+/// Entropy in bits of the distribution given by the number of candidates of each
+/// response.
+pub(crate) fn entropy_of_counts(counts: &[usize]) -> f64 {
+    let total: usize = counts.iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+
+    // Candidate counts are far below 2^52, the conversions are exact
+    let total = total as f64;
+    counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// # `hard_mode_validity`
+/// Checks a guess against the hard mode rules, while it is being typed.
+///
+/// ## Arguments
+/// * `guess` - The word being typed.
+/// * `patterns` - The guesses made so far with their states.
+///
+/// ## Returns
+/// * `Result<(), WordleError>` - The first hint the guess does not use, if any.
+pub fn hard_mode_validity(guess: &str, patterns: &[WordData]) -> Result<(), WordleError> {
+    let guess = Word::new(guess)?;
+    let patterns: Vec<Word> = patterns
+        .iter()
+        .map(convert_word_data)
+        .collect::<Result<_, _>>()?;
+
+    validate_guess_against_hard_mode(&guess, &patterns)
+}
+
+/// # `analyze_guess`
+/// Computes the expected and worst case remaining candidates of a guess, over the
+/// candidates left by the given frontend patterns.
+///
+/// ## Arguments
+/// * `word_bank` - The word bank whose answers are filtered.
+/// * `guess` - The word to guess.
+/// * `patterns` - The list of patterns to filter against.
+///
+/// ## Returns
+/// * `Result<GuessAnalysis, WordleError>` - The analysis or an error if a word is invalid.
+pub fn analyze_guess(
+    word_bank: &WordBank,
+    guess: &str,
+    patterns: &[WordData],
+) -> Result<GuessAnalysis, WordleError> {
+    let word = Word::new(guess)?;
+    let candidates = filter_word_list(word_bank, patterns)?;
+
+    Ok(GuessAnalysis {
+        guess: guess.to_ascii_lowercase(),
+        expected_remaining: compute_expected_remaining(&word, &candidates),
+        worst_case_remaining: compute_worst_case_remaining(&word, &candidates),
+    })
+}
+
+/// # `worst_case_remaining`
+/// Returns the worst case remaining candidates of a guess, over the candidates left by
+/// the given frontend patterns.
+///
+/// ## Arguments
+/// * `word_bank` - The word bank whose answers are filtered.
+/// * `guess` - The word to guess.
+/// * `patterns` - The list of patterns to filter against.
+///
+/// ## Returns
+/// * `Result<usize, WordleError>` - The worst case or an error if a word is invalid.
+pub fn worst_case_remaining(
+    word_bank: &WordBank,
+    guess: &str,
+    patterns: &[WordData],
+) -> Result<usize, WordleError> {
+    let guess = Word::new(guess)?;
+    let candidates = filter_word_list(word_bank, patterns)?;
+
+    Ok(compute_worst_case_remaining(&guess, &candidates))
+}
+
+/// # `pattern_statistics`
+/// Returns the response distribution of a guess over the candidates left by the
+/// given frontend patterns.
+///
+/// ## Arguments
+/// * `word_bank` - The word bank whose answers are filtered.
+/// * `guess` - The word to guess.
+/// * `patterns` - The list of patterns to filter against.
+///
+/// ## Returns
+/// * `Result<Vec<(String, usize)>, WordleError>` - The distribution or an error if a
+///   word is invalid.
+pub fn pattern_statistics(
+    word_bank: &WordBank,
+    guess: &str,
+    patterns: &[WordData],
+) -> Result<Vec<(String, usize)>, WordleError> {
+    let guess = Word::new(guess)?;
+    let candidates = filter_word_list(word_bank, patterns)?;
+
+    Ok(response_distribution(&guess, &candidates))
+}
+
+/// # `hamming_distance`
+/// Counts the positions where two words have different letters, states are ignored.
+///
+/// ## Arguments
+/// * `a` - The first word.
+/// * `b` - The second word.
+///
+/// ## Returns
+/// * `usize` - The number of differing positions, from 0 to 5.
+#[must_use]
+pub fn hamming_distance(a: &Word, b: &Word) -> usize {
+    a.into_iter().zip(b).filter(|(x, y)| !x.overlaps(y)).count()
+}
+
+/// # `closest_words`
+/// Returns the candidates closest to a word by Hamming distance, e.g. to suggest
+/// near-miss alternatives.
+///
+/// ## Arguments
+/// * `target` - The word to compare against.
+/// * `candidates` - The words to pick from, words that are not 5 letters are skipped.
+/// * `n` - The maximum number of words to return.
+///
+/// ## Returns
+/// * `Vec<(String, usize)>` - At most `n` words with their distance, closest first.
+#[must_use]
+pub fn closest_words(target: &Word, candidates: &[String], n: usize) -> Vec<(String, usize)> {
+    let mut distances: Vec<(String, usize)> = candidates
+        .iter()
+        .filter_map(|word| {
+            Word::new(word)
+                .ok()
+                .map(|w| (word.clone(), hamming_distance(target, &w)))
+        })
+        .collect();
+    // Stable so that ties keep the order of the candidates
+    distances.sort_by_key(|(_, distance)| *distance);
+    distances.truncate(n);
+
+    distances
+}
+
+// Square of a played letter, colored with ANSI escape codes
+#[cfg(unix)]
+fn board_square(letter: &Letter) -> String {
+    let c = letter.character.to_ascii_uppercase();
+    match letter.state {
+        LetterState::Correct => format!("\x1b[30;42m {c} \x1b[0m"),
+        LetterState::Misplaced => format!("\x1b[30;43m {c} \x1b[0m"),
+        LetterState::Absent => format!("\x1b[37;100m {c} \x1b[0m"),
+        LetterState::Unknown => format!("[{c}]"),
+    }
+}
+
+// Square of a played letter, terminals may not support ANSI escape codes
+#[cfg(not(unix))]
+fn board_square(letter: &Letter) -> String {
+    let c = letter.character.to_ascii_uppercase();
+    match letter.state {
+        LetterState::Correct => format!("[{c}]"),
+        LetterState::Misplaced => format!("({c})"),
+        LetterState::Absent | LetterState::Unknown => format!(" {c} "),
+    }
+}
+
+/// # `display_board`
+/// Renders the board for a terminal: one row per guess, colored by the states of
+/// its letters, then one empty row per remaining guess.
+///
+/// ## Arguments
+/// * `history` - The guesses made so far, with their states.
+/// * `max_guesses` - The number of guesses allowed in the game.
+///
+/// ## Returns
+/// * `String` - The rows of the board, separated by newlines.
+#[must_use]
+pub fn display_board(history: &[Word], max_guesses: usize) -> String {
+    let played = history
+        .iter()
+        .map(|guess| guess.into_iter().map(board_square).collect());
+    let empty = std::iter::repeat_n("[ ]".repeat(5), max_guesses.saturating_sub(history.len()));
+
+    played.chain(empty).collect::<Vec<String>>().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{all_absent, all_correct, all_misplaced};
+
+    #[test]
+    fn test_pattern_matching() {
+        // Test exact match
+        let word = Word::new("paint").unwrap();
+        let pattern = Word::from_pattern_code("paint", "GUUUU").unwrap();
+        assert_eq!(word.matches_pattern(&pattern), None);
+
+        // Test misplaced letter
+        let word = Word::new("paint").unwrap();
+        let pattern = Word::from_pattern_code("apart", "YUUUU").unwrap();
+        assert_eq!(word.matches_pattern(&pattern), None);
+
+        // Test absent letter
+        let word = Word::new("paint").unwrap();
+        let pattern = Word::from_pattern_code("horse", "AUUUU").unwrap();
+        assert_eq!(word.matches_pattern(&pattern), None);
+    }
+
+    #[test]
+    fn test_filter_words() {
+        let all_words = vec![
+            "paint".to_string(),
+            "taint".to_string(),
+            "saint".to_string(),
+            "print".to_string(),
+        ];
+
+        let pattern = Word::from_pattern_code("paint", "GGUUU").unwrap();
+
+        let filtered = filter_words(&all_words, &[pattern]);
+        assert!(filtered.contains(&"paint".to_string()));
+        assert!(!filtered.contains(&"saint".to_string()));
+    }
+
+    #[test]
+    fn test_pattern_matching_basic() {
+        // Test exact match with all correct
+        let word = Word::new("chart").unwrap();
+        let pattern = all_correct("chart");
+        assert_eq!(word.matches_pattern(&pattern), None);
+
+        // Test all misplaced
+        let word = Word::new("smart").unwrap();
+        let pattern = all_misplaced("tarms");
+        assert_eq!(word.matches_pattern(&pattern), None);
+
+        // Test all absent
+        let word = Word::new("chart").unwrap();
+        let pattern = all_absent("wound");
+        assert_eq!(word.matches_pattern(&pattern), None);
+    }
+
+    #[test]
+    fn test_pattern_matching_mixed_states() {
+        let word = Word::new("steam").unwrap();
+        // 's' and 't' correct, 'a' and 'm' misplaced, 'p' absent
+        let pattern = Word::from_pattern_code("stamp", "GGYYA").unwrap();
+        assert_eq!(word.matches_pattern(&pattern), None);
+    }
+
+    #[test]
     fn test_pattern_matching_duplicate_letters() {
         // Test duplicate letters in word
         let word = Word::new("books").unwrap();
-        let pattern = create_pattern(
-            "boost",
+        // 'b' and both 'o's correct, 's' misplaced, 't' absent
+        let pattern = Word::from_pattern_code("boost", "GGGYA").unwrap();
+
+        assert_eq!(word.matches_pattern(&pattern), None);
+
+        // Test duplicate letters with different states: "happy" against "paper" shows
+        // the second 'p' as misplaced since "paper" has a second 'p'
+        let word = Word::new("paper").unwrap();
+        let pattern = Word::from_pattern_code("happy", "AGGYA").unwrap();
+        assert_eq!(word.matches_pattern(&pattern), None);
+
+        // 'a' and the first 'p' correct, the second 'p' absent: the word has a single 'p'
+        let pattern = Word::from_pattern_code("happy", "AGGAA").unwrap();
+        assert!(word.matches_pattern(&pattern).is_some());
+        assert_eq!(Word::new("capon").unwrap().matches_pattern(&pattern), None);
+    }
+
+    #[test]
+    fn test_pattern_matching_edge_cases() {
+        // Test when pattern has unknown states
+        let word = Word::new("trace").unwrap();
+        let pattern = Word::from_pattern_code("track", "GUUUU").unwrap(); // Only first letter marked
+        assert_eq!(word.matches_pattern(&pattern), None);
+
+        // Test when pattern is same word but no states set
+        let word = Word::new("plane").unwrap();
+        let pattern = Word::new("plane").unwrap();
+        assert_eq!(word.matches_pattern(&pattern), None);
+    }
+
+    #[test]
+    fn test_filter_words_basic() {
+        let all_words = vec![
+            "paint".to_string(),
+            "taint".to_string(),
+            "saint".to_string(),
+            "print".to_string(),
+            "brain".to_string(),
+        ];
+
+        // Test single pattern, 'p' and 'a' correct
+        let pattern = Word::from_pattern_code("paint", "GGUUU").unwrap();
+
+        let filtered = filter_words(&all_words, &[pattern]);
+        assert_eq!(filtered, vec!["paint".to_string()]);
+    }
+
+    #[test]
+    fn test_filter_words_multiple_patterns() {
+        let all_words = vec![
+            "paint".to_string(),
+            "taint".to_string(),
+            "saint".to_string(),
+            "print".to_string(),
+            "brain".to_string(),
+        ];
+
+        // Test multiple patterns
+        let pattern1 = Word::from_pattern_code("saint", "UUUUG").unwrap(); // 't' correct at end
+        let pattern2 = Word::from_pattern_code("brain", "UAUUU").unwrap(); // 'r' absent
+
+        let filtered = filter_words(&all_words, &[pattern1, pattern2]);
+        assert!(filtered.contains(&"paint".to_string()));
+        assert!(filtered.contains(&"saint".to_string()));
+        assert!(!filtered.contains(&"print".to_string()));
+    }
+
+    #[test]
+    fn test_words_of_other_lengths() {
+        let mut mint: Word<4> = "MINT".parse().unwrap();
+        assert_eq!(mint.to_string(), "mint");
+        for (letter, state) in (&mut mint).into_iter().zip([
+            LetterState::Correct,
+            LetterState::Misplaced,
+            LetterState::Absent,
+            LetterState::Absent,
+        ]) {
+            letter.set_state(state);
+        }
+        let words: Vec<String> = ["mini", "maid", "mild", "mojo", "crane"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(filter_words_of_length(&words, &[mint]), vec!["maid"]);
+
+        let mut bottle: Word<6> = "bottle".parse().unwrap();
+        for letter in &mut bottle {
+            letter.set_state(LetterState::Correct);
+        }
+        let words: Vec<String> = ["bottle", "battle", "crane"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(filter_words_of_length(&words, &[bottle]), vec!["bottle"]);
+        assert_eq!(
+            "crane".parse::<Word<6>>(),
+            Err(WordleError::InvalidLength {
+                expected: 6,
+                got: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_filter_word_list_of_length() {
+        let word_bank = WordBank::load();
+        assert_eq!(
+            filter_word_list_of_length(&word_bank, &[], 5),
+            filter_word_list(&word_bank, &[])
+        );
+        assert_eq!(
+            filter_word_list_of_length(&word_bank, &[], 7),
+            Err(WordleError::UnsupportedWordLength(7))
+        );
+        // No 4 or 6 letter word list is bundled yet
+        for length in [4, 6] {
+            assert_eq!(
+                filter_word_list_of_length(&word_bank, &[], length),
+                Err(WordleError::UnsupportedWordLength(length))
+            );
+        }
+    }
+
+    #[test]
+    fn test_filter_words_edge_cases() {
+        // Test empty word list
+        let empty_words: Vec<String> = vec![];
+        let pattern = Word::new("tests").unwrap();
+        assert!(filter_words(&empty_words, &[pattern]).is_empty());
+
+        // Test empty patterns
+        let words = vec!["hello".to_string()];
+        let empty_patterns: Vec<Word> = vec![];
+        assert_eq!(filter_words(&words, &empty_patterns), words);
+
+        // Test invalid words in list
+        let invalid_words = vec![
+            "valid".to_string(),
+            "toolong".to_string(),
+            "shor".to_string(), // short
+        ];
+        let pattern = Word::new("valid").unwrap();
+        let filtered = filter_words(&invalid_words, &[pattern]);
+        assert_eq!(filtered.len(), 1);
+        assert!(filtered.contains(&"valid".to_string()));
+    }
+
+    #[test]
+    fn test_filter_words_complex_patterns() {
+        let all_words = vec![
+            "belle".to_string(),
+            "steel".to_string(),
+            "spell".to_string(),
+            "eagle".to_string(),
+            "whale".to_string(),
+        ];
+
+        // Test pattern with duplicate letters: 's' and 'p' absent, 'e' misplaced,
+        // first 'l' correct and second 'l' misplaced
+        let pattern1 = Word::from_pattern_code("spell", "AAYGY").unwrap();
+
+        let filtered = filter_words(&all_words, &[pattern1]);
+        assert!(filtered.contains(&"belle".to_string()));
+        assert!(!filtered.contains(&"spell".to_string()));
+    }
+
+    #[test]
+    fn test_filter_words_ranked() {
+        let all_words = vec![
+            "paint".to_string(),
+            "print".to_string(),
+            "point".to_string(),
+            "brain".to_string(),
+        ];
+        let pattern = Word::from_pattern_code("plant", "GUUUU").unwrap();
+
+        // Words with an 'r' rank first, ties keep the word list order
+        let scorer = |word: &Word, _: &[String]| {
+            if word.letter_at(1).character == 'r' {
+                1.0
+            } else {
+                0.0
+            }
+        };
+        let ranked = filter_words_ranked(&all_words, &[pattern], &scorer);
+
+        let words: Vec<&str> = ranked.iter().map(|(w, _)| w.as_str()).collect();
+        assert_eq!(words, vec!["print", "paint", "point"]);
+        assert!((ranked[0].1 - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_filter_words_with_priority() {
+        let words: Vec<String> = ["crane", "trace", "slate", "caret"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let pattern = all_absent("pious");
+
+        // Priority by the position of 't', ties keep the word list order
+        let sorted = filter_words_with_priority(&words, &[pattern], |word| {
+            word.find('t').map_or(0.0, |i| i as f64)
+        });
+        assert_eq!(
+            sorted,
+            vec![
+                "caret".to_string(),
+                "crane".to_string(),
+                "trace".to_string()
+            ]
+        );
+
+        let sorted = filter_words_with_priority(&words, &[], |_| 1.0);
+        assert_eq!(sorted, words);
+    }
+
+    #[test]
+    fn test_filter_word_list_with_options() {
+        let patterns = vec![crate::data::word_to_data(
+            &Word::from_pattern_code("saint", "AGGGG").unwrap(),
+        )];
+        let word_bank = WordBank::load();
+        let all =
+            filter_word_list_with_options(&word_bank, &patterns, FilterOptions::default()).unwrap();
+        assert_eq!(all.total, all.words.len());
+        assert!(all.total > 2);
+
+        let options = FilterOptions {
+            max_results: Some(2),
+        };
+        let top = filter_word_list_with_options(&word_bank, &patterns, options).unwrap();
+        assert_eq!(top.total, all.total);
+        assert_eq!(
+            top.words,
+            top_candidate_list(&word_bank, &patterns, 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_top_n_candidates() {
+        let all_words = vec![
+            "paint".to_string(),
+            "taint".to_string(),
+            "saint".to_string(),
+            "print".to_string(),
+        ];
+        let pattern = Word::from_pattern_code("saint", "UUUUG").unwrap();
+
+        let top = top_n_candidates(&all_words, &[pattern], 2, &score_by_letter_frequency);
+        assert_eq!(top.len(), 2);
+        assert!(top.iter().all(|w| all_words.contains(w)));
+
+        // Asking for more words than available returns every candidate
+        let all = top_n_candidates(&all_words, &[], 10, &score_by_letter_frequency);
+        assert_eq!(all.len(), all_words.len());
+
+        assert!(top_n_candidates(&all_words, &[], 0, &score_by_letter_frequency).is_empty());
+    }
+
+    #[test]
+    fn test_compute_response() {
+        let response = |guess: &str, secret: &str| {
+            let response =
+                compute_response(&Word::new(guess).unwrap(), &Word::new(secret).unwrap());
+            response
+                .into_iter()
+                .map(|l| l.state.to_code())
+                .collect::<String>()
+        };
+
+        assert_eq!(response("crane", "crane"), "GGGGG");
+        assert_eq!(response("crane", "trace"), "YGGAG");
+        assert_eq!(response("pious", "crane"), "AAAAA");
+
+        // Only one 'p' of the guess matches the single unmatched 'p' of the secret
+        assert_eq!(response("happy", "paper"), "AGGYA");
+        assert_eq!(response("happy", "spray"), "AYYAG");
+        // The correct 'e' takes priority over the misplaced one
+        assert_eq!(response("geese", "those"), "AAAGG");
+    }
+
+    #[test]
+    fn test_compute_response_duplicate_letters() {
+        let response = |guess: &str, secret: &str| {
+            evaluate_guess(&Word::new(guess).unwrap(), &Word::new(secret).unwrap()).pattern_code()
+        };
+
+        // Two 'e's in both words, both in place
+        assert_eq!(response("speed", "creep"), "AYGGA");
+        // The correct 'l' takes the single 'l' of the secret, the other one is absent
+        assert_eq!(response("hello", "world"), "AAAGY");
+        // The first 'e' takes the single unmatched 'e', the second one is absent
+        assert_eq!(response("eerie", "there"), "YAYAG");
+        // Three 'e's in place, the extra ones are absent
+        assert_eq!(response("eeeee", "geese"), "AGGAG");
+        // Three 'l's against two, the first unmatched copy is misplaced
+        assert_eq!(response("lolly", "llama"), "GAYAA");
+        // The correct 'r' is matched first, then each misplaced copy takes one
+        assert_eq!(response("array", "rarer"), "YYGAA");
+    }
+
+    #[test]
+    fn test_compute_response_other_lengths() {
+        let response = |guess: Word<4>, secret: Word<4>| {
+            compute_response(&guess, &secret)
+                .into_iter()
+                .map(|l| l.state.to_code())
+                .collect::<String>()
+        };
+        assert_eq!(
+            response("noon".parse().unwrap(), "onto".parse().unwrap()),
+            "YYYA"
+        );
+
+        let better: Word<6> = "better".parse().unwrap();
+        let letter: Word<6> = "letter".parse().unwrap();
+        let pattern = compute_response(&better, &letter);
+        assert_eq!(pattern[0].state, LetterState::Absent);
+        assert!((&pattern)
+            .into_iter()
+            .skip(1)
+            .all(|l| l.state == LetterState::Correct));
+        assert!(letter.matches_pattern_bool(&pattern));
+        assert!(!better.matches_pattern_bool(&pattern));
+    }
+
+    #[test]
+    fn test_dedup_candidates() {
+        let words = vec![
+            "slate".to_string(),
+            "crane".to_string(),
+            "slate".to_string(),
+            "pious".to_string(),
+            "crane".to_string(),
+        ];
+        assert_eq!(
+            dedup_candidates(words),
+            vec![
+                "slate".to_string(),
+                "crane".to_string(),
+                "pious".to_string()
+            ]
+        );
+        assert!(dedup_candidates(vec![]).is_empty());
+    }
+
+    #[test]
+    fn test_filter_words_excluding() {
+        let all_words = vec![
+            "paint".to_string(),
+            "taint".to_string(),
+            "saint".to_string(),
+            "print".to_string(),
+        ];
+        let pattern = Word::from_pattern_code("saint", "UGGGG").unwrap();
+
+        let filtered = filter_words_excluding(&all_words, &[pattern], &["saint".to_string()]);
+        assert_eq!(filtered, vec!["paint".to_string(), "taint".to_string()]);
+
+        // Excluding nothing is the same as filter_words
+        assert_eq!(filter_words_excluding(&all_words, &[], &[]), all_words);
+    }
+
+    #[test]
+    fn test_filter_words_with_validator() {
+        let all_words = vec![
+            "paint".to_string(),
+            "taint".to_string(),
+            "saint".to_string(),
+            "print".to_string(),
+        ];
+        let pattern = Word::from_pattern_code("saint", "UGGGG").unwrap();
+
+        let filtered = filter_words_with_validator(&all_words, &[pattern], |w| w.starts_with('s'));
+        assert_eq!(filtered, vec!["saint".to_string()]);
+
+        // A vowel as second letter
+        let filtered = filter_words_with_validator(&all_words, &[], |w| "aeiou".contains(&w[1..2]));
+        assert_eq!(filtered.len(), 3);
+    }
+
+    #[test]
+    fn test_filter_words_deterministic() {
+        let all_words = vec![
+            "taint".to_string(),
+            "print".to_string(),
+            "paint".to_string(),
+            "saint".to_string(),
+        ];
+        let pattern = Word::from_pattern_code("saint", "UGGGG").unwrap();
+
+        assert_eq!(
+            filter_words_deterministic(&all_words, &[pattern]),
             vec![
-                (0, LetterState::Correct),   //'b' correct
-                (1, LetterState::Correct),   // First 'o' correct
-                (2, LetterState::Correct),   // Second 'o' correct
-                (3, LetterState::Misplaced), // 's' misplaced
-                (4, LetterState::Absent),    // 't' absent
-            ],
+                "paint".to_string(),
+                "saint".to_string(),
+                "taint".to_string()
+            ]
         );
+    }
 
-        assert!(word.matches_pattern(&pattern));
+    #[test]
+    fn test_validate_guess_against_hard_mode() {
+        // Secret "geese": "eerie" reveals two 'e's, both must be reused
+        let eerie = Word::from_pattern_code("eerie", "YGAAG").unwrap();
+        let patterns = std::slice::from_ref(&eerie);
 
-        // Test duplicate letters with different states
-        let word = Word::new("paper").unwrap();
-        let pattern = create_pattern(
-            "happy",
-            vec![
-                (0, LetterState::Absent),  // 'h' absent
-                (1, LetterState::Correct), // 'a' correct
-                (2, LetterState::Correct), // 'p' correct
-                (3, LetterState::Absent),  // 'p' absent
-                (4, LetterState::Absent),  // 'y' absent
-            ],
+        let guess = Word::new("geese").unwrap();
+        assert_eq!(validate_guess_against_hard_mode(&guess, patterns), Ok(()));
+        assert_eq!(
+            validate_guess_against_hard_mode(&Word::new("theme").unwrap(), patterns),
+            Err(WordleError::HardModeCorrectLetter {
+                letter: 'e',
+                position: 1
+            })
         );
-        assert!(word.matches_pattern(&pattern));
+        assert_eq!(
+            validate_guess_against_hard_mode(&Word::new("leave").unwrap(), patterns),
+            Err(WordleError::HardModeMissingLetter('e'))
+        );
+        assert_eq!(validate_guess_against_hard_mode(&guess, &[]), Ok(()));
     }
 
     #[test]
-    fn test_pattern_matching_edge_cases() {
-        // Test when pattern has unknown states
-        let word = Word::new("trace").unwrap();
-        let pattern = create_pattern(
-            "track",
-            vec![
-                (0, LetterState::Correct), // Only first letter marked
-            ],
+    fn test_diagnose_game_state() {
+        let bank = WordBank::from_words(vec!["trace".to_string(), "crate".to_string()]);
+        let crane = Word::from_pattern_code("crane", "YGGAG").unwrap();
+        assert_eq!(
+            diagnose_game_state(&bank, std::slice::from_ref(&crane)),
+            GameDiagnosis::Solvable
         );
-        assert!(word.matches_pattern(&pattern));
 
-        // Test when pattern is same word but no states set
-        let word = Word::new("plane").unwrap();
-        let pattern = Word::new("plane").unwrap();
-        assert!(word.matches_pattern(&pattern));
+        // "brace" is consistent with "crane" but not in the bank
+        let trace = Word::from_pattern_code("trace", "AGGGG").unwrap();
+        let patterns = [crane.clone(), trace];
+        assert!(is_consistent_pattern_set(&patterns));
+        assert_eq!(
+            diagnose_game_state(&bank, &patterns),
+            GameDiagnosis::NotInWordBank
+        );
+        assert!(!detect_impossible_game_state(&bank, &patterns));
+
+        // 'r' cannot be Correct at position 1 in one guess and absent in the next
+        let print = all_absent("print");
+        let patterns = [crane.clone(), print];
+        assert!(!is_consistent_pattern_set(&patterns));
+        assert!(detect_impossible_game_state(&bank, &patterns));
+
+        // Two different Correct letters at the same position
+        let brake = Word::from_pattern_code("brake", "GAAAA").unwrap();
+        let crisp = Word::from_pattern_code("crisp", "GAAAA").unwrap();
+        assert!(!is_consistent_pattern_set(&[brake, crisp]));
     }
 
     #[test]
-    fn test_filter_words_basic() {
+    fn test_suggest_correction() {
+        let crane = Word::from_pattern_code("crane", "YGGAG").unwrap();
+        let trace = Word::from_pattern_code("trace", "AGGGG").unwrap();
+        assert!(suggest_correction(&[crane.clone(), trace.clone()]).is_empty());
+
+        // "print" marks the 'r' two other guesses found Correct as absent
+        let print = all_absent("print");
+        assert_eq!(
+            suggest_correction(&[crane.clone(), print.clone(), trace.clone()]),
+            vec!["print".to_string()]
+        );
+        // With only two guesses, either one can be the mistake
+        assert_eq!(
+            suggest_correction(&[crane, print]),
+            vec!["crane".to_string(), "print".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_new_with_guess_and_answer() {
+        assert_eq!(
+            Word::new_with_guess_and_answer("crane", "trace"),
+            Ok(Word::from_pattern_code("crane", "YGGAG").unwrap())
+        );
+        assert_eq!(
+            Word::new_with_guess_and_answer("crane", "trees!"),
+            Err(WordleError::InvalidLength {
+                expected: 5,
+                got: 6
+            })
+        );
+    }
+
+    #[test]
+    fn test_filter_words_until_n() {
         let all_words = vec![
-            "paint".to_string(),
             "taint".to_string(),
-            "saint".to_string(),
             "print".to_string(),
-            "brain".to_string(),
+            "paint".to_string(),
+            "saint".to_string(),
+        ];
+        let patterns = [
+            Word::from_pattern_code("saint", "UGGGG").unwrap(),
+            Word::from_pattern_code("paint", "AGGGG").unwrap(),
+            Word::from_pattern_code("taint", "AGGGG").unwrap(),
+        ];
+
+        assert_eq!(
+            filter_words_until_n(&all_words, &patterns, 1),
+            (vec!["saint".to_string()], 2)
+        );
+        assert_eq!(filter_words_until_n(&all_words, &patterns, 3).1, 0);
+        // Never reached: every pattern is applied
+        assert_eq!(
+            filter_words_until_n(&all_words, &patterns, 0),
+            (vec!["saint".to_string()], 3)
+        );
+    }
+
+    #[test]
+    fn test_response_distribution() {
+        let candidates = vec![
+            "trace".to_string(),
+            "react".to_string(),
+            "caret".to_string(),
+            "crate".to_string(),
+            "grate".to_string(),
+            "irate".to_string(),
         ];
+        let guess = Word::new("crate").unwrap();
 
-        // Test single pattern
-        let pattern = create_pattern(
-            "paint",
+        assert_eq!(
+            response_distribution(&guess, &candidates),
             vec![
-                (0, LetterState::Correct), // 'p' correct
-                (1, LetterState::Correct), // 'a' correct
-            ],
+                ("AGGGG".to_string(), 2),
+                ("GGGGG".to_string(), 1),
+                ("GYYYY".to_string(), 1),
+                ("YGGYG".to_string(), 1),
+                ("YYGYY".to_string(), 1),
+            ]
         );
+        assert!(response_distribution(&guess, &[]).is_empty());
+    }
 
-        let filtered = filter_words(&all_words, &[pattern]);
-        assert_eq!(filtered, vec!["paint".to_string()]);
+    #[test]
+    fn test_compute_worst_case_remaining() {
+        let candidates = vec![
+            "crate".to_string(),
+            "grate".to_string(),
+            "irate".to_string(),
+            "trace".to_string(),
+        ];
+
+        // "grate" and "irate" both give AGGGG
+        let guess = Word::new("crate").unwrap();
+        assert_eq!(compute_worst_case_remaining(&guess, &candidates), 2);
+        assert_eq!(pattern_partition(&guess, &candidates)["AGGGG"].len(), 2);
+
+        assert_eq!(compute_worst_case_remaining(&guess, &[]), 0);
     }
 
     #[test]
-    fn test_filter_words_multiple_patterns() {
+    fn test_compute_expected_remaining() {
+        let candidates = vec![
+            "crate".to_string(),
+            "grate".to_string(),
+            "irate".to_string(),
+            "trace".to_string(),
+        ];
+
+        // Partitions of sizes 1, 2 and 1: (1 + 4 + 1) / 4
+        let guess = Word::new("crate").unwrap();
+        assert!((compute_expected_remaining(&guess, &candidates) - 1.5).abs() < f64::EPSILON);
+        assert!(compute_expected_remaining(&guess, &[]).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_compute_entropy() {
+        let candidates = vec![
+            "crate".to_string(),
+            "grate".to_string(),
+            "irate".to_string(),
+            "trace".to_string(),
+        ];
+
+        // Partitions of sizes 1, 2 and 1: 3 * (-p log2 p) with the same 0.5 bit each
+        let guess = Word::new("crate").unwrap();
+        assert!((compute_entropy(&guess, &candidates) - 1.5).abs() < 1e-12);
+        assert!(compute_entropy(&guess, &[]).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_pattern_mismatch() {
+        let pattern = Word::from_pattern_code("crane", "GYAUU").unwrap();
+
+        let mismatch = Word::new("slate")
+            .unwrap()
+            .matches_pattern(&pattern)
+            .unwrap();
+        assert_eq!(mismatch.position, 0);
+        assert_eq!(mismatch.expected_state, LetterState::Correct);
+        assert_eq!(mismatch.reason, "expected 'c' here");
+
+        let mismatch = Word::new("cider").unwrap().matches_pattern(&pattern);
+        assert_eq!(mismatch, None);
+        assert!(Word::new("cider").unwrap().matches_pattern_bool(&pattern));
+
+        let mismatch = Word::new("crust")
+            .unwrap()
+            .matches_pattern(&pattern)
+            .unwrap();
+        assert_eq!(mismatch.position, 1);
+        assert_eq!(mismatch.reason, "'r' cannot be here");
+    }
+
+    #[test]
+    fn test_matches_pattern_agrees_with_constraints() {
+        let words = crate::load_words();
+        for (word, code) in [
+            ("crane", "GYAUU"),
+            ("geese", "AYAAG"),
+            ("happy", "AGGAA"),
+            ("sassy", "YAGAA"),
+        ] {
+            let pattern = Word::from_pattern_code(word, code).unwrap();
+            let constraints = LetterConstraints::from_patterns(std::slice::from_ref(&pattern));
+            for candidate in words.iter().filter_map(|w| Word::new(w).ok()) {
+                assert_eq!(
+                    candidate.matches_pattern_bool(&pattern),
+                    candidate.matches_constraints(&constraints),
+                    "{candidate:?} against {word} {code}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_debug_filter_words() {
         let all_words = vec![
             "paint".to_string(),
-            "taint".to_string(),
             "saint".to_string(),
             "print".to_string(),
-            "brain".to_string(),
+            "toast".to_string(),
+        ];
+        let patterns = [
+            Word::from_pattern_code("stain", "AUUUU").unwrap(),
+            Word::from_pattern_code("plant", "GAYGG").unwrap(),
         ];
 
-        // Test multiple patterns
-        let pattern1 = create_pattern(
-            "saint",
-            vec![
-                (4, LetterState::Correct), // 't' correct at end
-            ],
+        let trace = debug_filter_words(&all_words, &patterns);
+        assert_eq!(
+            trace.constraints,
+            "Known: p__nt | Must include: {a≥1, n≥1, p≥1, t≥1} | Forbidden: {l, s}"
         );
-        let pattern2 = create_pattern(
-            "brain",
+        assert_eq!(trace.remaining, vec!["paint".to_string()]);
+        assert_eq!(
+            trace.eliminated_by,
             vec![
-                (1, LetterState::Absent), // 'r' absent
-            ],
+                ("saint".to_string(), 0),
+                ("print".to_string(), 1),
+                ("toast".to_string(), 0)
+            ]
         );
-
-        let filtered = filter_words(&all_words, &[pattern1, pattern2]);
-        assert!(filtered.contains(&"paint".to_string()));
-        assert!(filtered.contains(&"saint".to_string()));
-        assert!(!filtered.contains(&"print".to_string()));
+        assert_eq!(trace.remaining, filter_words(&all_words, &patterns));
     }
 
     #[test]
-    fn test_filter_words_edge_cases() {
-        // Test empty word list
-        let empty_words: Vec<String> = vec![];
-        let pattern = Word::new("tests").unwrap();
-        assert!(filter_words(&empty_words, &[pattern]).is_empty());
-
-        // Test empty patterns
-        let words = vec!["hello".to_string()];
-        let empty_patterns: Vec<Word> = vec![];
-        assert_eq!(filter_words(&words, &empty_patterns), words);
+    fn test_hamming_distance() {
+        let crane = Word::new("crane").unwrap();
+        assert_eq!(hamming_distance(&crane, &crane), 0);
+        assert_eq!(hamming_distance(&crane, &Word::new("crate").unwrap()), 1);
+        assert_eq!(hamming_distance(&crane, &Word::new("pious").unwrap()), 5);
 
-        // Test invalid words in list
-        let invalid_words = vec![
-            "valid".to_string(),
-            "toolong".to_string(),
-            "shor".to_string(), // short
+        let candidates = vec![
+            "pious".to_string(),
+            "crate".to_string(),
+            "brand".to_string(),
+            "grace".to_string(),
         ];
-        let pattern = Word::new("valid").unwrap();
-        let filtered = filter_words(&invalid_words, &[pattern]);
-        assert_eq!(filtered.len(), 1);
-        assert!(filtered.contains(&"valid".to_string()));
+        assert_eq!(
+            closest_words(&crane, &candidates, 2),
+            vec![("crate".to_string(), 1), ("brand".to_string(), 2)]
+        );
     }
 
     #[test]
-    fn test_filter_words_complex_patterns() {
-        let all_words = vec![
-            "belle".to_string(),
-            "steel".to_string(),
-            "spell".to_string(),
-            "eagle".to_string(),
-            "whale".to_string(),
+    fn test_apply_keyboard_constraint() {
+        let words = vec![
+            "crane".to_string(),
+            "pious".to_string(),
+            "geese".to_string(),
         ];
+        // 'e' is Correct once, so only 'g' and 's' are Absent
+        let keyboard =
+            KeyboardState::from_patterns(&[Word::from_pattern_code("geese", "AAAAG").unwrap()]);
 
-        // Test pattern with duplicate letters
-        let pattern1 = create_pattern(
-            "spell",
-            vec![
-                (0, LetterState::Absent),    // 's' absent
-                (1, LetterState::Absent),    // 'p' absent
-                (2, LetterState::Misplaced), // 'e' misplaced
-                (3, LetterState::Correct),   // 'l' correct
-                (4, LetterState::Misplaced), // 'l' misplaced
-            ],
+        assert_eq!(
+            apply_keyboard_constraint(&words, &keyboard),
+            vec!["crane".to_string()]
+        );
+        assert_eq!(
+            apply_keyboard_constraint(&words, &KeyboardState::new()),
+            words
         );
+    }
 
-        let filtered = filter_words(&all_words, &[pattern1]);
-        assert!(filtered.contains(&"belle".to_string()));
-        assert!(!filtered.contains(&"spell".to_string()));
+    #[test]
+    fn test_count_of() {
+        let word = Word::new("geese").unwrap();
+        assert_eq!(word.count_of('e'), 3);
+        assert_eq!(word.count_of('G'), 1);
+        assert_eq!(word.count_of('a'), 0);
+    }
+
+    #[test]
+    fn test_to_sorted_chars() {
+        let word = Word::new("Crane").unwrap();
+        assert_eq!(word.to_sorted_chars(), ['a', 'c', 'e', 'n', 'r']);
+        assert_eq!(
+            word.to_sorted_chars(),
+            Word::new("nacre").unwrap().to_sorted_chars()
+        );
+        assert_eq!(
+            Word::new("geese").unwrap().to_sorted_chars(),
+            ['e', 'e', 'e', 'g', 's']
+        );
+    }
+
+    #[test]
+    fn test_contains_letters() {
+        let word = Word::new("crane").unwrap();
+        assert!(word.contains_letter('A'));
+        assert!(!word.contains_letter('s'));
+
+        assert!(word.contains_all_of(&['e', 'c', 'R']));
+        assert!(word.contains_all_of(&[]));
+        assert!(!word.contains_all_of(&['c', 's']));
+        assert!(!word.contains_all_of(&['c', '1']));
+
+        assert!(word.contains_none_of(&['s', 't']));
+        assert!(!word.contains_none_of(&['s', 'n']));
+    }
+
+    #[test]
+    fn test_display_board() {
+        let history = [Word::from_pattern_code("crane", "GYAUA").unwrap()];
+        let board = display_board(&history, 3);
+        let rows: Vec<&str> = board.lines().collect();
+
+        assert_eq!(rows.len(), 3);
+        assert!(rows[0].contains('C') && rows[0].contains("[N]"));
+        assert_eq!(rows[1], "[ ][ ][ ][ ][ ]");
+        assert_eq!(rows[2], "[ ][ ][ ][ ][ ]");
+
+        #[cfg(unix)]
+        assert!(rows[0].starts_with("\x1b[30;42m C \x1b[0m\x1b[30;43m R \x1b[0m"));
+
+        // More guesses than allowed never happen, but must not panic
+        assert_eq!(display_board(&history, 0).lines().count(), 1);
+    }
+}
+
+#[cfg(test)]
+mod duplicate_letter_tests {
+    use super::*;
+    use crate::create_pattern;
+    use LetterState::{Absent as A, Correct as G, Misplaced as Y};
+
+    // Checks a candidate against a pattern with every state given explicitly, through
+    // both the explainer and the compiled constraints
+    fn assert_matches(guess: &str, states: [LetterState; 5], candidate: &str, expected: bool) {
+        let pattern = create_pattern(guess, states.into_iter().enumerate().collect());
+        let word = Word::new(candidate).unwrap();
+
+        assert_eq!(
+            word.matches_pattern_bool(&pattern),
+            expected,
+            "{candidate} against {}",
+            pattern.pattern_code()
+        );
+        assert_eq!(
+            !filter_words(&[candidate.to_string()], std::slice::from_ref(&pattern)).is_empty(),
+            expected,
+            "{candidate} against the constraints of {}",
+            pattern.pattern_code()
+        );
+        // A full pattern matches exactly the secrets that would give this response
+        assert_eq!(
+            compute_response(&word_for(guess), &word) == pattern,
+            expected
+        );
+    }
+
+    fn word_for(guess: &str) -> Word {
+        Word::new(guess).unwrap()
+    }
+
+    // Same letter twice in the guess, once in the secret
+
+    #[test]
+    fn test_correct_then_absent_duplicate() {
+        assert_matches("hello", [A, A, A, G, Y], "world", true);
+    }
+
+    #[test]
+    fn test_absent_duplicate_is_not_misplaced() {
+        assert_matches("hello", [A, A, Y, A, Y], "world", false);
+    }
+
+    #[test]
+    fn test_misplaced_then_absent_duplicate() {
+        assert_matches("hello", [A, A, Y, A, Y], "lordy", true);
+    }
+
+    #[test]
+    fn test_missing_misplaced_letter() {
+        assert_matches("hello", [A, A, A, G, A], "world", false);
+    }
+
+    #[test]
+    fn test_correct_and_misplaced_duplicate() {
+        assert_matches("happy", [A, G, G, Y, A], "paper", true);
+    }
+
+    #[test]
+    fn test_absent_duplicate_caps_count() {
+        assert_matches("happy", [A, G, G, G, A], "paper", false);
+    }
+
+    #[test]
+    fn test_misplaced_duplicate_in_wrong_place() {
+        assert_matches("happy", [A, G, Y, Y, A], "paper", false);
+    }
+
+    #[test]
+    fn test_two_misplaced_then_correct() {
+        assert_matches("happy", [A, Y, Y, A, G], "spray", true);
+    }
+
+    #[test]
+    fn test_two_misplaced_with_two_in_secret() {
+        assert_matches("llama", [Y, Y, A, A, A], "jolly", true);
+    }
+
+    // Same letter twice in both words
+
+    #[test]
+    fn test_both_duplicates_in_place() {
+        assert_matches("speed", [A, Y, G, G, A], "creep", true);
+    }
+
+    #[test]
+    fn test_absent_second_copy_with_two_in_secret() {
+        assert_matches("speed", [A, Y, G, A, A], "creep", false);
+    }
+
+    // Same letter once in the guess, twice in the secret
+
+    #[test]
+    fn test_single_correct_with_two_in_secret() {
+        assert_matches("crane", [A, A, A, A, G], "geese", true);
+    }
+
+    #[test]
+    fn test_single_correct_with_one_in_secret() {
+        assert_matches("crane", [A, A, A, A, G], "those", true);
+    }
+
+    #[test]
+    fn test_single_misplaced_with_two_in_secret() {
+        assert_matches("stole", [Y, A, A, A, G], "geese", true);
+    }
+
+    #[test]
+    fn test_single_misplaced_with_one_in_secret() {
+        assert_matches("stole", [Y, A, A, A, G], "erase", true);
+    }
+
+    // Same letter three times in the guess
+
+    #[test]
+    fn test_five_copies_three_in_place() {
+        assert_matches("eeeee", [A, G, G, A, G], "geese", true);
+    }
+
+    #[test]
+    fn test_five_copies_absent_in_place() {
+        assert_matches("eeeee", [A, G, G, A, G], "eerie", false);
+    }
+
+    #[test]
+    fn test_three_copies_misplaced_absent_correct() {
+        assert_matches("eerie", [Y, A, Y, A, G], "there", true);
+    }
+
+    #[test]
+    fn test_three_copies_too_many_present() {
+        assert_matches("eerie", [Y, A, Y, Y, G], "there", false);
+    }
+
+    #[test]
+    fn test_three_copies_correct_twice() {
+        assert_matches("eerie", [G, G, A, A, G], "emcee", false);
+    }
+
+    // Correct at one position and Absent elsewhere, vs Misplaced and Absent elsewhere
+
+    #[test]
+    fn test_correct_and_absent_duplicates() {
+        assert_matches("geese", [A, A, A, G, G], "those", true);
+    }
+
+    #[test]
+    fn test_correct_and_misplaced_need_two_copies() {
+        assert_matches("geese", [A, Y, A, G, G], "those", false);
+    }
+
+    #[test]
+    fn test_two_misplaced_need_two_copies() {
+        assert_matches("eerie", [Y, Y, A, A, A], "bleak", false);
+    }
+
+    #[test]
+    fn test_correct_and_absent_duplicates_absent_in_place() {
+        assert_matches("geese", [A, A, A, G, G], "these", false);
+    }
+
+    #[test]
+    fn test_correct_then_absent_in_word() {
+        assert_matches("llama", [G, A, A, A, A], "lucky", true);
+    }
+
+    #[test]
+    fn test_misplaced_then_absent_in_word() {
+        assert_matches("llama", [Y, A, A, A, A], "pulse", true);
+    }
+
+    #[test]
+    fn test_correct_and_misplaced_with_three_in_secret() {
+        assert_matches("llama", [G, Y, A, A, A], "lolly", true);
+    }
+
+    #[test]
+    fn test_correct_and_misplaced_with_absent_other_letter() {
+        assert_matches("llama", [G, Y, A, A, A], "loyal", false);
+    }
+
+    #[test]
+    fn test_two_correct_with_three_in_secret() {
+        assert_matches("llama", [G, G, A, A, A], "lulls", false);
+    }
+
+    #[test]
+    fn test_triple_letter_responses() {
+        let guess = word_for("llama");
+        let words: Vec<Word> = crate::load_words()
+            .iter()
+            .filter_map(|w| Word::new(w).ok())
+            .collect();
+        for secret in ["lolly", "lulls", "hello", "atoll", "label", "pulse"] {
+            let pattern = compute_response(&guess, &word_for(secret));
+            for candidate in &words {
+                assert_eq!(
+                    candidate.matches_pattern_bool(&pattern),
+                    compute_response(&guess, candidate) == pattern,
+                    "{candidate:?} against llama {}",
+                    pattern.pattern_code()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_misplaced_then_absent_at_correct_place() {
+        assert_matches("llama", [Y, A, A, A, A], "lucky", false);
+    }
+
+    #[test]
+    fn test_misplaced_then_absent_caps_count() {
+        assert_matches("llama", [Y, A, A, A, A], "jolly", false);
     }
 }
@@ -0,0 +1,1002 @@
+//! Module containing the state of a game in progress.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    config::{ColorScheme, WordleConfig},
+    constraints::LetterConstraints,
+    data::{word_to_data, IntoWord, WordData},
+    game_logic::{
+        filter_words, filter_words_by_constraints, filter_words_with_priority,
+        validate_guess_against_hard_mode,
+    },
+    keyboard::KeyboardState,
+    solver::GameSolver,
+    word_bank::WordBank,
+    LetterState, Word, WordleError,
+};
+
+/// Number of guesses allowed in a standard Wordle game.
+pub const DEFAULT_MAX_GUESSES: usize = 6;
+
+// Colors of the board rendered by `to_png`, as on the Wordle website
+#[cfg(feature = "image")]
+const BOARD_BACKGROUND: [u8; 3] = [0xff, 0xff, 0xff];
+#[cfg(feature = "image")]
+const BOARD_EMPTY: [u8; 3] = [0xd3, 0xd6, 0xda];
+
+// Color of a played square of the board rendered by `to_png`
+#[cfg(feature = "image")]
+fn square_color(state: LetterState) -> [u8; 3] {
+    match state {
+        LetterState::Correct => [0x6a, 0xaa, 0x64],
+        LetterState::Misplaced => [0xc9, 0xb4, 0x58],
+        LetterState::Absent => [0x78, 0x7c, 0x7e],
+        LetterState::Unknown => BOARD_EMPTY,
+    }
+}
+
+/// # `GamePhase`
+/// Represents the progress of a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GamePhase {
+    InProgress, // Guesses can still be made
+    Won,        // The last guess was all Correct
+    Lost,       // Every guess was used without finding the word
+}
+
+/// # `GameMode`
+/// Represents the rules a game is played with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMode {
+    #[default]
+    Normal, // Any word of the bank can be guessed
+    Hard, // Revealed hints must be used in every following guess
+}
+
+/// # `GuessQuality`
+/// How much a guess narrowed down the candidates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuessQuality {
+    pub guess: String,
+    pub candidates_before: usize,
+    pub candidates_after: usize,
+    pub eliminated: f64, // Fraction of the candidates the guess ruled out, from 0 to 1
+}
+
+/// # `score_guess_quality`
+/// Returns the fraction of the candidates a guess ruled out.
+///
+/// ## Arguments
+/// * `candidates_before` - The number of candidates before the guess.
+/// * `candidates_after` - The number of candidates left after the guess.
+///
+/// ## Returns
+/// * `f64` - From 0 (nothing learned) to 1, 0 if there was no candidate.
+#[must_use]
+pub fn score_guess_quality(candidates_before: usize, candidates_after: usize) -> f64 {
+    if candidates_before == 0 {
+        return 0.0;
+    }
+
+    // Candidate counts are far below 2^52, the conversions are exact
+    1.0 - candidates_after.min(candidates_before) as f64 / candidates_before as f64
+}
+
+/// # `PostGameSummary`
+/// Everything shown once a game is over.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostGameSummary {
+    pub secret: Option<String>, // Only known when the game computes the responses itself
+    pub guesses: Vec<String>,
+    pub guess_qualities: Vec<GuessQuality>,
+    pub total_turns: usize,
+    pub result: GamePhase,
+    pub share_text: String,
+}
+
+// What `serialize_to_json` writes, the candidates are derived from the guesses
+#[derive(Deserialize)]
+struct SavedGame {
+    guesses: Vec<WordData>,
+    max_guesses: usize,
+    phase: GamePhase,
+    game_mode: GameMode,
+}
+
+/// # `GameState`
+/// Tracks the guesses of a game and the candidates still matching them.
+#[derive(Clone)]
+pub struct GameState {
+    word_bank: Vec<String>,
+    guesses: Vec<Word>,
+    candidates: Vec<String>,
+    max_guesses: usize,
+    secret: Option<String>,
+    phase: GamePhase,
+    game_mode: GameMode,
+    hints_used: usize,
+    // The number of candidates before each guess
+    candidate_counts: Vec<usize>,
+    // Shared so that the game stays cheap to clone
+    solver: Option<Arc<dyn GameSolver + Send + Sync>>,
+}
+
+// Solvers are not `Debug`, only tell whether one is configured
+impl std::fmt::Debug for GameState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GameState")
+            .field("word_bank", &self.word_bank)
+            .field("guesses", &self.guesses)
+            .field("candidates", &self.candidates)
+            .field("max_guesses", &self.max_guesses)
+            .field("secret", &self.secret)
+            .field("phase", &self.phase)
+            .field("game_mode", &self.game_mode)
+            .field("hints_used", &self.hints_used)
+            .field("candidate_counts", &self.candidate_counts)
+            .field("solver", &self.solver.is_some())
+            .finish()
+    }
+}
+
+impl GameState {
+    /// # `new`
+    /// Creates a new game where every word of the bank is a candidate.
+    ///
+    /// ## Arguments
+    /// * `word_bank` - The list of valid words.
+    #[must_use]
+    pub fn new(word_bank: Vec<String>) -> Self {
+        Self {
+            candidates: word_bank.clone(),
+            word_bank,
+            guesses: Vec::new(),
+            max_guesses: DEFAULT_MAX_GUESSES,
+            secret: None,
+            phase: GamePhase::InProgress,
+            game_mode: GameMode::Normal,
+            hints_used: 0,
+            candidate_counts: Vec::new(),
+            solver: None,
+        }
+    }
+
+    /// # `new_with_constraints`
+    /// Creates a new game where the candidates already satisfy known constraints,
+    /// e.g. when the guesses that revealed them are not known.
+    /// The hard mode rules only apply to the guesses made afterwards.
+    ///
+    /// ## Arguments
+    /// * `word_bank` - The list of valid words.
+    /// * `constraints` - What is already known about the secret.
+    #[must_use]
+    pub fn new_with_constraints(word_bank: Vec<String>, constraints: &LetterConstraints) -> Self {
+        let mut state = Self::new(word_bank);
+        state.candidates = filter_words_by_constraints(&state.candidates, constraints);
+
+        state
+    }
+
+    /// # `from_config`
+    /// Creates a new game following the rules of a configuration.
+    ///
+    /// ## Arguments
+    /// * `word_bank` - The list of valid words.
+    /// * `config` - The configuration to follow.
+    #[must_use]
+    pub fn from_config(word_bank: Vec<String>, config: &WordleConfig) -> Self {
+        let mut state = Self::new(word_bank).with_game_mode(config.game_mode);
+        state.max_guesses = config.max_guesses;
+
+        state
+    }
+
+    /// # `from_word_bank`
+    /// Creates a new game where guesses are checked against the allowed words
+    /// and the answers are the candidates.
+    ///
+    /// ## Arguments
+    /// * `word_bank` - The answers and allowed guesses.
+    /// * `config` - The configuration to follow.
+    #[must_use]
+    pub fn from_word_bank(word_bank: &WordBank, config: &WordleConfig) -> Self {
+        let mut state = Self::from_config(word_bank.allowed.clone(), config);
+        state.candidates = word_bank.answers().to_vec();
+
+        state
+    }
+
+    /// # `with_game_mode`
+    /// Sets the rules the game is played with.
+    ///
+    /// ## Arguments
+    /// * `game_mode` - The rules to play with.
+    #[must_use]
+    pub fn with_game_mode(mut self, game_mode: GameMode) -> Self {
+        self.game_mode = game_mode;
+        self
+    }
+
+    /// # `with_solver`
+    /// Sets the solver `best_current_guess` asks for recommendations.
+    ///
+    /// ## Arguments
+    /// * `solver` - The solver picking the guesses.
+    #[must_use]
+    pub fn with_solver(mut self, solver: Box<dyn GameSolver + Send + Sync>) -> Self {
+        self.solver = Some(Arc::from(solver));
+        self
+    }
+
+    /// # `with_secret`
+    /// Sets the word to find, so that the game can compute the responses itself.
+    ///
+    /// ## Arguments
+    /// * `secret` - The word to find.
+    #[must_use]
+    pub fn with_secret(mut self, secret: String) -> Self {
+        self.secret = Some(secret);
+        self
+    }
+
+    /// # `replay`
+    /// Reconstructs a game from a sequence of guesses and their pattern codes.
+    ///
+    /// ## Arguments
+    /// * `word_bank` - The list of valid words.
+    /// * `moves` - The `(word, code)` pairs in order, e.g. `("crane", "GYAAU")`.
+    ///
+    /// ## Returns
+    /// * `Result<GameState, WordleError>` - The game after every move, or the first error.
+    pub fn replay(word_bank: Vec<String>, moves: &[(&str, &str)]) -> Result<Self, WordleError> {
+        let mut state = Self::new(word_bank);
+        for (word, code) in moves {
+            let mut guess = Word::new(word)?;
+            guess.apply_pattern_code(code)?;
+            state.add_guess(guess)?;
+        }
+
+        Ok(state)
+    }
+
+    /// # `add_guess`
+    /// Records a guess (with its states) and narrows down the candidates.
+    ///
+    /// ## Arguments
+    /// * `guess` - The guessed word with the states given as feedback.
+    ///
+    /// ## Returns
+    /// * `Result<(), WordleError>` - An error if the word is not in the bank, breaks
+    ///   the hard mode rules or no guesses are left.
+    pub fn add_guess(&mut self, guess: Word) -> Result<(), WordleError> {
+        if self.phase == GamePhase::Won {
+            return Err(WordleError::GameOver);
+        }
+        if self.guesses_remaining() == 0 {
+            return Err(WordleError::NoGuessesLeft);
+        }
+
+        let text = guess.to_string();
+        if !self.word_bank.contains(&text) {
+            return Err(WordleError::NotInWordBank(text));
+        }
+        if self.game_mode == GameMode::Hard {
+            self.validate_hard_mode_guess(&guess)?;
+        }
+
+        self.candidate_counts.push(self.candidates.len());
+        self.candidates = filter_words(&self.candidates, std::slice::from_ref(&guess));
+        let all_correct = (0..5).all(|i| guess.letter_at(i).state == LetterState::Correct);
+        self.guesses.push(guess);
+
+        if all_correct {
+            self.phase = GamePhase::Won;
+        } else if self.guesses_remaining() == 0 {
+            self.phase = GamePhase::Lost;
+        }
+
+        Ok(())
+    }
+
+    /// # `validate_hard_mode_guess`
+    /// Checks that a guess uses every hint revealed so far: Correct letters must stay
+    /// in place and Misplaced letters must appear somewhere in the guess.
+    ///
+    /// ## Arguments
+    /// * `guess` - The guessed word, its states are ignored.
+    ///
+    /// ## Returns
+    /// * `Result<(), WordleError>` - The first hint the guess does not use, if any.
+    pub fn validate_hard_mode_guess(&self, guess: &Word) -> Result<(), WordleError> {
+        validate_guess_against_hard_mode(guess, &self.guesses)
+    }
+
+    /// # `guess`
+    /// Plays a guess against the secret word: computes the response and records it.
+    ///
+    /// ## Arguments
+    /// * `guess` - The guessed word.
+    ///
+    /// ## Returns
+    /// * `Result<Word, WordleError>` - The guess with its states, or an error if the
+    ///   game has no secret or the guess is invalid.
+    pub fn guess(&mut self, guess: &str) -> Result<Word, WordleError> {
+        let secret = self.secret.as_deref().ok_or(WordleError::NoSecret)?;
+        let response = Word::new_with_guess_and_answer(guess, secret)?;
+        self.add_guess(response.clone())?;
+
+        Ok(response)
+    }
+
+    /// # `guesses`
+    /// Returns the guesses made so far, in order.
+    #[must_use]
+    pub fn guesses(&self) -> &[Word] {
+        &self.guesses
+    }
+
+    /// # `candidates`
+    /// Returns the words still matching every guess.
+    #[must_use]
+    pub fn candidates(&self) -> &[String] {
+        &self.candidates
+    }
+
+    /// # `current_candidates_count`
+    /// Returns the number of words still matching every guess. This is the length of
+    /// the `candidates` slice, no field needs to be kept in sync.
+    #[must_use]
+    pub fn current_candidates_count(&self) -> usize {
+        self.candidates.len()
+    }
+
+    /// # `candidates_by_priority`
+    /// Returns the words still matching every guess, the most promising first.
+    ///
+    /// ## Arguments
+    /// * `priority` - The priority of a candidate, higher comes first.
+    #[must_use]
+    pub fn candidates_by_priority<F: Fn(&str) -> f64>(&self, priority: F) -> Vec<String> {
+        filter_words_with_priority(&self.candidates, &[], priority)
+    }
+
+    /// # `word_bank`
+    /// Returns the list of valid words of the game.
+    #[must_use]
+    pub fn word_bank(&self) -> &[String] {
+        &self.word_bank
+    }
+
+    /// # `phase`
+    /// Returns the progress of the game.
+    #[must_use]
+    pub fn phase(&self) -> GamePhase {
+        self.phase
+    }
+
+    /// # `keyboard`
+    /// Returns the keyboard state resulting from every guess.
+    #[must_use]
+    pub fn keyboard(&self) -> KeyboardState {
+        KeyboardState::from_patterns(&self.guesses)
+    }
+
+    /// # `game_mode`
+    /// Returns the rules the game is played with.
+    #[must_use]
+    pub fn game_mode(&self) -> GameMode {
+        self.game_mode
+    }
+
+    /// # `set_game_mode`
+    /// Changes the rules of the game, only allowed before the first guess.
+    ///
+    /// ## Arguments
+    /// * `game_mode` - The rules to play with.
+    ///
+    /// ## Returns
+    /// * `Result<(), WordleError>` - An error if a guess was already made.
+    pub fn set_game_mode(&mut self, game_mode: GameMode) -> Result<(), WordleError> {
+        if !self.guesses.is_empty() {
+            return Err(WordleError::GameAlreadyStarted);
+        }
+        self.game_mode = game_mode;
+
+        Ok(())
+    }
+
+    /// # `max_guesses`
+    /// Returns the number of guesses allowed in the game.
+    #[must_use]
+    pub fn max_guesses(&self) -> usize {
+        self.max_guesses
+    }
+
+    /// # `turn_number`
+    /// Returns the 1-based number of the current turn, the next guess to submit.
+    #[must_use]
+    pub fn turn_number(&self) -> usize {
+        self.guesses.len() + 1
+    }
+
+    /// # `is_last_turn`
+    /// Returns whether the next guess is the last one allowed.
+    #[must_use]
+    pub fn is_last_turn(&self) -> bool {
+        self.turn_number() == self.max_guesses
+    }
+
+    /// # `guesses_remaining`
+    /// Returns the number of guesses that can still be submitted.
+    #[must_use]
+    pub fn guesses_remaining(&self) -> usize {
+        self.max_guesses - self.guesses.len()
+    }
+
+    /// # `hint`
+    /// Returns the guess the solver recommends, without making it.
+    ///
+    /// ## Arguments
+    /// * `solver` - The solver picking the guess.
+    ///
+    /// ## Returns
+    /// * `Option<String>` - The recommended guess, or `None` if no candidate is left.
+    #[must_use]
+    pub fn hint(&self, solver: &dyn GameSolver) -> Option<String> {
+        solver.next_guess(&self.candidates)
+    }
+
+    /// # `best_current_guess`
+    /// Returns the guess the configured solver recommends, without making it.
+    ///
+    /// ## Returns
+    /// * `Option<String>` - The recommended guess, or `None` if no solver is configured
+    ///   or no candidate is left.
+    #[must_use]
+    pub fn best_current_guess(&self) -> Option<String> {
+        self.solver.as_deref().and_then(|solver| self.hint(solver))
+    }
+
+    /// # `record_hint`
+    /// Counts a hint shown to the player.
+    pub fn record_hint(&mut self) {
+        self.hints_used += 1;
+    }
+
+    /// # `hints_used`
+    /// Returns the number of hints shown in the game.
+    #[must_use]
+    pub fn hints_used(&self) -> usize {
+        self.hints_used
+    }
+
+    /// # `share_text`
+    /// Returns the result of the game as Wordle shares it: the score, then one row
+    /// of emojis per guess. Hard mode games are marked with a `*`.
+    ///
+    /// ## Arguments
+    /// * `scheme` - The colors of the emojis.
+    #[must_use]
+    pub fn share_text(&self, scheme: ColorScheme) -> String {
+        let score = match self.phase {
+            GamePhase::Won => self.guesses.len().to_string(),
+            GamePhase::InProgress | GamePhase::Lost => "X".to_string(),
+        };
+        let hard = if self.game_mode == GameMode::Hard {
+            "*"
+        } else {
+            ""
+        };
+        let rows: Vec<String> = self
+            .guesses
+            .iter()
+            .map(|guess| {
+                (0..5)
+                    .map(|i| guess.letter_at(i).state.to_emoji_with_scheme(scheme))
+                    .collect()
+            })
+            .collect();
+
+        format!(
+            "Wordle {score}/{}{hard}\n\n{}",
+            self.max_guesses,
+            rows.join("\n")
+        )
+    }
+
+    /// # `to_png`
+    /// Renders the board as a PNG image: one row of squares per allowed guess, colored
+    /// by the states of the guesses, the rows not played yet are left empty.
+    ///
+    /// ## Arguments
+    /// * `width` - The width of the image, in pixels.
+    /// * `height` - The height of the image, in pixels.
+    ///
+    /// ## Returns
+    /// * `Vec<u8>` - The bytes of the PNG file.
+    ///
+    /// ## Panics
+    /// If `width` or `height` is 0.
+    #[cfg(feature = "image")]
+    #[must_use]
+    pub fn to_png(&self, width: u32, height: u32) -> Vec<u8> {
+        let rows = self.max_guesses.max(1);
+        let (width_px, height_px) = (width as usize, height as usize);
+        let (cell_width, cell_height) = ((width_px / 5).max(1), (height_px / rows).max(1));
+        let gap = cell_width.min(cell_height) / 10;
+
+        let mut pixels = Vec::with_capacity(width_px * height_px * 3);
+        for y in 0..height_px {
+            for x in 0..width_px {
+                let (col, row) = (x / cell_width, y / cell_height);
+                let in_square =
+                    col < 5 && row < rows && x % cell_width >= gap && y % cell_height >= gap;
+                let color = if !in_square {
+                    BOARD_BACKGROUND
+                } else if let Some(guess) = self.guesses.get(row) {
+                    square_color(guess.letter_at(col).state)
+                } else {
+                    BOARD_EMPTY
+                };
+                pixels.extend_from_slice(&color);
+            }
+        }
+
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&pixels))
+            .expect("the image must not be empty");
+
+        bytes
+    }
+
+    /// # `guess_qualities`
+    /// Returns how much each guess narrowed down the candidates, in order.
+    #[must_use]
+    pub fn guess_qualities(&self) -> Vec<GuessQuality> {
+        self.guesses
+            .iter()
+            .enumerate()
+            .map(|(i, guess)| {
+                let before = self.candidate_counts[i];
+                let after = self
+                    .candidate_counts
+                    .get(i + 1)
+                    .copied()
+                    .unwrap_or(self.candidates.len());
+                GuessQuality {
+                    guess: guess.to_string(),
+                    candidates_before: before,
+                    candidates_after: after,
+                    eliminated: score_guess_quality(before, after),
+                }
+            })
+            .collect()
+    }
+
+    /// # `summarize`
+    /// Returns the metrics of the game for the post-game screen.
+    ///
+    /// ## Arguments
+    /// * `scheme` - The colors of the emojis of the share text.
+    #[must_use]
+    pub fn summarize(&self, scheme: ColorScheme) -> PostGameSummary {
+        let guess_qualities = self.guess_qualities();
+
+        PostGameSummary {
+            secret: self.secret.clone(),
+            guesses: guess_qualities.iter().map(|q| q.guess.clone()).collect(),
+            guess_qualities,
+            total_turns: self.guesses.len(),
+            result: self.phase,
+            share_text: self.share_text(scheme),
+        }
+    }
+
+    /// # `serialize_to_json`
+    /// Serializes the guesses (as `WordData`) and the rules of the game, the inverse
+    /// of `deserialize_from_json`. The word bank, secret and solver are not saved.
+    #[must_use]
+    pub fn serialize_to_json(&self) -> String {
+        let guesses: Vec<WordData> = self.guesses.iter().map(word_to_data).collect();
+
+        json!({
+            "guesses": guesses,
+            "max_guesses": self.max_guesses,
+            "phase": self.phase,
+            "game_mode": self.game_mode,
+        })
+        .to_string()
+    }
+
+    /// # `deserialize_from_json`
+    /// Reconstructs a game saved by `serialize_to_json`, replaying every guess
+    /// against the word bank to derive the candidates.
+    ///
+    /// ## Arguments
+    /// * `json` - The saved game.
+    /// * `word_bank` - The list of valid words.
+    ///
+    /// ## Returns
+    /// * `Result<GameState, WordleError>` - The game, or an error if the JSON is invalid,
+    ///   a guess is not in the word bank or the saved phase does not match the guesses.
+    pub fn deserialize_from_json(json: &str, word_bank: Vec<String>) -> Result<Self, WordleError> {
+        let saved: SavedGame =
+            serde_json::from_str(json).map_err(|e| WordleError::InvalidSavedGame(e.to_string()))?;
+
+        let mut state = Self::new(word_bank).with_game_mode(saved.game_mode);
+        state.max_guesses = saved.max_guesses;
+        for guess in saved.guesses {
+            state.add_guess(guess.into_word()?)?;
+        }
+        if state.phase != saved.phase {
+            return Err(WordleError::InvalidSavedGame(format!(
+                "saved as {:?} but the guesses give {:?}",
+                saved.phase, state.phase
+            )));
+        }
+
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::FrequencySolver;
+
+    fn word_bank() -> Vec<String> {
+        ["crane", "slate", "pious", "trace", "react", "caret"]
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_new_game() {
+        let state = GameState::new(word_bank());
+        assert!(state.guesses().is_empty());
+        assert_eq!(state.candidates(), word_bank().as_slice());
+        assert_eq!(state.max_guesses(), DEFAULT_MAX_GUESSES);
+    }
+
+    #[test]
+    fn test_replay() {
+        // Secret "trace": crane -> c misplaced, r, a and e correct, n absent
+        let state = GameState::replay(word_bank(), &[("crane", "YGGAG")]).unwrap();
+        assert_eq!(state.guesses().len(), 1);
+        assert_eq!(state.candidates(), &["trace".to_string()]);
+    }
+
+    #[test]
+    fn test_replay_errors() {
+        assert_eq!(
+            GameState::replay(word_bank(), &[("zzzzz", "AAAAA")]).unwrap_err(),
+            WordleError::NotInWordBank("zzzzz".to_string())
+        );
+        assert_eq!(
+            GameState::replay(word_bank(), &[("cran", "AAAA")]).unwrap_err(),
+            WordleError::InvalidLength {
+                expected: 5,
+                got: 4
+            }
+        );
+        assert_eq!(
+            GameState::replay(word_bank(), &[("crane", "GYA")]).unwrap_err(),
+            WordleError::InvalidPatternLength { got: 3 }
+        );
+    }
+
+    #[test]
+    fn test_guess_against_secret() {
+        let mut state = GameState::new(word_bank()).with_secret("trace".to_string());
+
+        let response = state.guess("crane").unwrap();
+        assert_eq!(response, Word::from_pattern_code("crane", "YGGAG").unwrap());
+        assert_eq!(state.phase(), GamePhase::InProgress);
+        assert_eq!(state.keyboard().state_of('n'), LetterState::Absent);
+
+        state.guess("trace").unwrap();
+        assert_eq!(state.phase(), GamePhase::Won);
+        assert_eq!(state.guess("slate"), Err(WordleError::GameOver));
+    }
+
+    #[test]
+    fn test_share_text() {
+        let mut state = GameState::new(word_bank())
+            .with_game_mode(GameMode::Hard)
+            .with_secret("trace".to_string());
+        state.guess("crane").unwrap();
+        assert_eq!(
+            state.share_text(ColorScheme::Default),
+            "Wordle X/6*\n\n🟨🟩🟩⬛🟩"
+        );
+
+        state.guess("trace").unwrap();
+        assert_eq!(
+            state.share_text(ColorScheme::HighContrast),
+            "Wordle 2/6*\n\n🟦🟧🟧⬛🟧\n🟧🟧🟧🟧🟧"
+        );
+    }
+
+    #[test]
+    fn test_guess_errors() {
+        let mut state = GameState::new(word_bank());
+        assert_eq!(state.guess("crane"), Err(WordleError::NoSecret));
+
+        let mut state = state.with_secret("trace".to_string());
+        assert_eq!(
+            state.guess("cr4ne"),
+            Err(WordleError::NonAlphabeticCharacter('4'))
+        );
+        assert_eq!(
+            state.guess("zzzzz"),
+            Err(WordleError::NotInWordBank("zzzzz".to_string()))
+        );
+        assert!(state.guesses().is_empty());
+    }
+
+    #[test]
+    fn test_add_guess_limit() {
+        let mut state = GameState::new(word_bank());
+        for _ in 0..DEFAULT_MAX_GUESSES {
+            state.add_guess(Word::new("slate").unwrap()).unwrap();
+        }
+        assert_eq!(state.phase(), GamePhase::Lost);
+        assert_eq!(
+            state.add_guess(Word::new("slate").unwrap()),
+            Err(WordleError::NoGuessesLeft)
+        );
+    }
+
+    #[test]
+    fn test_turn_helpers() {
+        let mut state = GameState::new(word_bank());
+        assert_eq!(state.turn_number(), 1);
+        assert_eq!(state.guesses_remaining(), DEFAULT_MAX_GUESSES);
+        assert!(!state.is_last_turn());
+
+        for _ in 1..DEFAULT_MAX_GUESSES {
+            state.add_guess(Word::new("slate").unwrap()).unwrap();
+        }
+        assert_eq!(state.turn_number(), DEFAULT_MAX_GUESSES);
+        assert_eq!(state.guesses_remaining(), 1);
+        assert!(state.is_last_turn());
+    }
+
+    #[test]
+    fn test_hard_mode_validation() {
+        // Secret "trace": crane reveals 'c' misplaced and 'r', 'a', 'e' correct
+        let state = GameState::replay(word_bank(), &[("crane", "YGGAG")]).unwrap();
+
+        assert!(state
+            .validate_hard_mode_guess(&Word::new("trace").unwrap())
+            .is_ok());
+        assert_eq!(
+            state.validate_hard_mode_guess(&Word::new("slate").unwrap()),
+            Err(WordleError::HardModeCorrectLetter {
+                letter: 'r',
+                position: 1
+            })
+        );
+        assert_eq!(
+            state.validate_hard_mode_guess(&Word::new("grade").unwrap()),
+            Err(WordleError::HardModeMissingLetter('c'))
+        );
+    }
+
+    #[test]
+    fn test_add_guess_hard_mode() {
+        let slate = Word::from_pattern_code("slate", "AAGGG").unwrap();
+        let crane = Word::from_pattern_code("crane", "YGGAG").unwrap();
+
+        let mut state = GameState::new(word_bank()).with_game_mode(GameMode::Hard);
+        state.add_guess(crane.clone()).unwrap();
+        assert!(state.add_guess(slate.clone()).is_err());
+        assert_eq!(state.guesses().len(), 1);
+
+        // The same guess is fine in normal mode
+        let mut state = GameState::new(word_bank());
+        state.add_guess(crane).unwrap();
+        state.add_guess(slate).unwrap();
+        assert_eq!(state.guesses().len(), 2);
+    }
+
+    #[test]
+    fn test_set_game_mode() {
+        let mut state = GameState::new(word_bank());
+        assert_eq!(state.game_mode(), GameMode::Normal);
+
+        state.set_game_mode(GameMode::Hard).unwrap();
+        assert_eq!(state.game_mode(), GameMode::Hard);
+
+        state.add_guess(Word::new("slate").unwrap()).unwrap();
+        assert_eq!(
+            state.set_game_mode(GameMode::Normal),
+            Err(WordleError::GameAlreadyStarted)
+        );
+        assert_eq!(state.game_mode(), GameMode::Hard);
+    }
+
+    #[test]
+    fn test_from_config() {
+        let config = WordleConfig {
+            max_guesses: 4,
+            game_mode: GameMode::Hard,
+            ..WordleConfig::default()
+        };
+        let state = GameState::from_config(word_bank(), &config);
+        assert_eq!(state.max_guesses(), 4);
+        assert_eq!(state.game_mode(), GameMode::Hard);
+    }
+
+    #[test]
+    fn test_from_word_bank() {
+        let bank = WordBank::from_lists(vec!["trace".to_string()], word_bank());
+        let mut state = GameState::from_word_bank(&bank, &WordleConfig::default());
+        assert_eq!(state.candidates(), &["trace".to_string()]);
+
+        // Allowed guesses that cannot be the answer are accepted
+        state
+            .add_guess(Word::from_pattern_code("crane", "YGGAG").unwrap())
+            .unwrap();
+        assert_eq!(state.candidates(), &["trace".to_string()]);
+    }
+
+    #[test]
+    fn test_hint() {
+        let mut state = GameState::new(word_bank());
+        state
+            .add_guess(Word::from_pattern_code("slate", "AAGYG").unwrap())
+            .unwrap();
+
+        assert_eq!(state.hint(&FrequencySolver), Some("trace".to_string()));
+        assert_eq!(state.guesses().len(), 1);
+
+        assert_eq!(state.hints_used(), 0);
+        state.record_hint();
+        assert_eq!(state.hints_used(), 1);
+    }
+
+    #[test]
+    fn test_best_current_guess() {
+        let state = GameState::new(word_bank());
+        assert_eq!(state.best_current_guess(), None);
+
+        let mut state = state.with_solver(Box::new(FrequencySolver));
+        state
+            .add_guess(Word::from_pattern_code("slate", "AAGYG").unwrap())
+            .unwrap();
+        assert_eq!(state.best_current_guess(), Some("trace".to_string()));
+    }
+
+    #[test]
+    fn test_current_candidates_count() {
+        let mut state = GameState::new(word_bank());
+        assert_eq!(state.current_candidates_count(), word_bank().len());
+
+        state
+            .add_guess(Word::from_pattern_code("pious", "AAAAA").unwrap())
+            .unwrap();
+        assert_eq!(state.current_candidates_count(), 4);
+        assert_eq!(state.current_candidates_count(), state.candidates().len());
+    }
+
+    #[test]
+    fn test_summarize() {
+        let mut state = GameState::new(word_bank()).with_secret("trace".to_string());
+        state.guess("pious").unwrap();
+        state.guess("crane").unwrap();
+        state.guess("trace").unwrap();
+
+        let summary = state.summarize(ColorScheme::Default);
+        assert_eq!(summary.secret, Some("trace".to_string()));
+        assert_eq!(summary.guesses, vec!["pious", "crane", "trace"]);
+        assert_eq!(summary.total_turns, 3);
+        assert_eq!(summary.result, GamePhase::Won);
+        assert_eq!(summary.share_text, state.share_text(ColorScheme::Default));
+
+        let counts: Vec<(usize, usize)> = summary
+            .guess_qualities
+            .iter()
+            .map(|q| (q.candidates_before, q.candidates_after))
+            .collect();
+        assert_eq!(counts, vec![(6, 4), (4, 1), (1, 1)]);
+        assert!((summary.guess_qualities[1].eliminated - 0.75).abs() < f64::EPSILON);
+        assert!(summary.guess_qualities[2].eliminated.abs() < f64::EPSILON);
+
+        assert!(score_guess_quality(0, 0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_candidates_by_priority() {
+        let state = GameState::replay(word_bank(), &[("pious", "AAAAA")]).unwrap();
+        assert_eq!(
+            state.candidates_by_priority(|word| if word.starts_with('c') { 1.0 } else { 0.0 }),
+            vec!["crane", "caret", "trace", "react"]
+        );
+    }
+
+    #[test]
+    fn test_new_with_constraints() {
+        let constraints =
+            LetterConstraints::from_patterns(&[Word::from_pattern_code("slate", "AAGYG").unwrap()]);
+        let mut state = GameState::new_with_constraints(word_bank(), &constraints);
+        assert_eq!(state.candidates(), &["trace".to_string()]);
+        assert!(state.guesses().is_empty());
+
+        state
+            .add_guess(Word::from_pattern_code("trace", "GGGGG").unwrap())
+            .unwrap();
+        assert_eq!(state.phase(), GamePhase::Won);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let state = GameState::replay(word_bank(), &[("crane", "YGGAG"), ("trace", "GGGGG")])
+            .unwrap()
+            .with_game_mode(GameMode::Hard);
+        let json = state.serialize_to_json();
+
+        let restored = GameState::deserialize_from_json(&json, word_bank()).unwrap();
+        assert_eq!(restored.guesses(), state.guesses());
+        assert_eq!(restored.candidates(), state.candidates());
+        assert_eq!(restored.phase(), GamePhase::Won);
+        assert_eq!(restored.game_mode(), GameMode::Hard);
+        assert_eq!(restored.max_guesses(), DEFAULT_MAX_GUESSES);
+    }
+
+    #[test]
+    fn test_deserialize_from_json_errors() {
+        assert!(matches!(
+            GameState::deserialize_from_json("not json", word_bank()),
+            Err(WordleError::InvalidSavedGame(_))
+        ));
+
+        let json = GameState::replay(word_bank(), &[("crane", "YGGAG")])
+            .unwrap()
+            .serialize_to_json();
+        assert_eq!(
+            GameState::deserialize_from_json(&json, vec!["slate".to_string()]).unwrap_err(),
+            WordleError::NotInWordBank("crane".to_string())
+        );
+        assert!(matches!(
+            GameState::deserialize_from_json(&json.replace("in_progress", "won"), word_bank()),
+            Err(WordleError::InvalidSavedGame(_))
+        ));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_to_png() {
+        let state = GameState::replay(word_bank(), &[("crane", "YGGAG")]).unwrap();
+        let png = state.to_png(50, 60);
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        let decoder = png::Decoder::new(png.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (50, 60));
+
+        // Squares are 10x10 with a 1 pixel gap: the first one is Misplaced
+        assert_eq!(&pixels[..3], &BOARD_BACKGROUND);
+        let first = (5 * 50 + 5) * 3;
+        assert_eq!(
+            &pixels[first..first + 3],
+            &square_color(LetterState::Misplaced)
+        );
+        let second_row = (15 * 50 + 5) * 3;
+        assert_eq!(&pixels[second_row..second_row + 3], &BOARD_EMPTY);
+    }
+}
@@ -0,0 +1,105 @@
+//! Module containing the state of the on-screen keyboard.
+
+use crate::{LetterState, Word};
+
+/// # `KeyboardState`
+/// Tracks the best known state of every letter of the alphabet across guesses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeyboardState {
+    states: [LetterState; 26],
+}
+
+impl KeyboardState {
+    /// # `new`
+    /// Creates a keyboard where every letter is Unknown.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # `from_patterns`
+    /// Creates a keyboard from all the given patterns.
+    ///
+    /// ## Arguments
+    /// * `patterns` - The list of patterns to apply.
+    ///
+    /// ## Returns
+    /// * `KeyboardState` - The resulting keyboard.
+    #[must_use]
+    pub fn from_patterns(patterns: &[Word]) -> Self {
+        let mut keyboard = Self::new();
+        for pattern in patterns {
+            keyboard.update_from_pattern(pattern);
+        }
+
+        keyboard
+    }
+
+    /// # `update_from_pattern`
+    /// Updates the keys with the states of a pattern. A key only changes if the
+    /// new state is more certain, e.g. a duplicate letter marked Absent does not
+    /// override the same letter marked Correct elsewhere.
+    ///
+    /// ## Arguments
+    /// * `pattern` - The pattern to apply.
+    pub fn update_from_pattern(&mut self, pattern: &Word) {
+        for i in 0..5 {
+            let letter = pattern.letter_at(i);
+            let index = (letter.character as u8 - b'a') as usize;
+            // A key never goes back to a less certain state
+            self.states[index] = self.states[index].max(letter.state);
+        }
+    }
+
+    /// # `state_of`
+    /// Returns the state of a letter, `Unknown` for non ASCII letters.
+    ///
+    /// ## Arguments
+    /// * `c` - The letter to look up.
+    ///
+    /// ## Returns
+    /// * `LetterState` - The state of the key.
+    #[must_use]
+    pub fn state_of(&self, c: char) -> LetterState {
+        if !c.is_ascii_alphabetic() {
+            return LetterState::Unknown;
+        }
+
+        self.states[(c.to_ascii_lowercase() as u8 - b'a') as usize]
+    }
+
+    /// # `iter`
+    /// Iterates over the keys from 'a' to 'z' with their state.
+    pub fn iter(&self) -> impl Iterator<Item = (char, LetterState)> + '_ {
+        ('a'..='z').zip(self.states.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyboard_update() {
+        let keyboard = KeyboardState::from_patterns(&[
+            Word::from_pattern_code("crane", "AYAAG").unwrap(),
+            Word::from_pattern_code("rouse", "GAAAG").unwrap(),
+        ]);
+
+        assert_eq!(keyboard.state_of('r'), LetterState::Correct);
+        assert_eq!(keyboard.state_of('e'), LetterState::Correct);
+        assert_eq!(keyboard.state_of('c'), LetterState::Absent);
+        assert_eq!(keyboard.state_of('z'), LetterState::Unknown);
+        assert_eq!(keyboard.state_of('R'), LetterState::Correct);
+        assert_eq!(keyboard.state_of('1'), LetterState::Unknown);
+    }
+
+    #[test]
+    fn test_keyboard_never_downgrades() {
+        // The second 'e' is absent but the first one is correct
+        let keyboard =
+            KeyboardState::from_patterns(&[Word::from_pattern_code("eerie", "GAAAA").unwrap()]);
+        assert_eq!(keyboard.state_of('e'), LetterState::Correct);
+        assert_eq!(keyboard.iter().count(), 26);
+    }
+}
@@ -1,24 +1,136 @@
+#[cfg(feature = "cache")]
+pub mod cache;
+pub mod config;
+pub mod constraints;
+#[cfg(feature = "daily")]
+pub mod daily;
 pub mod data;
+pub mod error;
+#[cfg(feature = "fuzzy")]
+pub mod fuzzy;
 pub mod game_logic;
+pub mod game_state;
+pub mod keyboard;
+pub mod pattern_table;
+pub mod scoring;
+pub mod solver;
+pub mod stats;
 pub mod tauri;
+pub mod word_bank;
 
+use config::ColorScheme;
+pub use error::WordleError;
 pub use tauri::run;
 
-const WORDS_FILE: &str = "assets/all_words.json";
+use std::{
+    fmt,
+    ops::{Index, IndexMut, Range, RangeFull},
+    str::FromStr,
+};
+
+pub(crate) const WORDS_FILE: &str = "assets/all_words.json";
 
 /// # `LetterState`
 /// Represents the state of a letter in a word.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub enum LetterState {
-    Unknown,   // Initial state
+    #[default]
+    Unknown, // Initial state
     Correct,   // Green - right letter, right position
     Misplaced, // Yellow - right letter, wrong position
     Absent,    // Gray - letter not in word
 }
 
+impl LetterState {
+    /// # `from_code`
+    /// Parses a pattern code character: `G` (Correct), `Y` (Misplaced), `A` (Absent)
+    /// or `U` (Unknown). Lowercase characters are accepted as well.
+    ///
+    /// ## Arguments
+    /// * `code` - The pattern code character.
+    ///
+    /// ## Returns
+    /// * `Result<LetterState, WordleError>` - The matching state or an error.
+    pub fn from_code(code: char) -> Result<Self, WordleError> {
+        match code.to_ascii_uppercase() {
+            'G' => Ok(Self::Correct),
+            'Y' => Ok(Self::Misplaced),
+            'A' => Ok(Self::Absent),
+            'U' => Ok(Self::Unknown),
+            _ => Err(WordleError::InvalidPatternCode(code)),
+        }
+    }
+
+    /// # `to_code`
+    /// Returns the pattern code character of the state, the inverse of `from_code`.
+    #[must_use]
+    pub fn to_code(self) -> char {
+        match self {
+            Self::Correct => 'G',
+            Self::Misplaced => 'Y',
+            Self::Absent => 'A',
+            Self::Unknown => 'U',
+        }
+    }
+
+    /// # `to_emoji_with_scheme`
+    /// Returns the emoji of the state, as in the shared results of Wordle.
+    ///
+    /// ## Arguments
+    /// * `scheme` - The colors to use, Unknown and Absent are the same in every scheme.
+    #[must_use]
+    pub fn to_emoji_with_scheme(self, scheme: ColorScheme) -> char {
+        match (self, scheme) {
+            (Self::Correct, ColorScheme::Default) => '🟩',
+            (Self::Misplaced, ColorScheme::Default) => '🟨',
+            (Self::Correct, ColorScheme::HighContrast) => '🟧',
+            (Self::Misplaced, ColorScheme::HighContrast) => '🟦',
+            (Self::Correct, ColorScheme::Accessible) => '✅',
+            (Self::Misplaced, ColorScheme::Accessible) => '🔶',
+            (Self::Absent, _) => '⬛',
+            (Self::Unknown, _) => '⬜',
+        }
+    }
+
+    // How much the state tells about a letter
+    fn certainty(self) -> u8 {
+        match self {
+            Self::Unknown => 0,
+            Self::Absent => 1,
+            Self::Misplaced => 2,
+            Self::Correct => 3,
+        }
+    }
+}
+
+/// States are ordered by certainty: Unknown < Absent < Misplaced < Correct.
+impl PartialOrd for LetterState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LetterState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.certainty().cmp(&other.certainty())
+    }
+}
+
+/// Displays the state as in the JSON sent to the frontend, e.g. `misplaced`.
+impl fmt::Display for LetterState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Unknown => "unknown",
+            Self::Correct => "correct",
+            Self::Misplaced => "misplaced",
+            Self::Absent => "absent",
+        })
+    }
+}
+
 /// # `Letter`
 /// Represents a letter in a word with its state.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Letter {
     pub character: char,
     pub state: LetterState,
@@ -26,20 +138,33 @@ pub struct Letter {
 
 impl Letter {
     /// Create a new Letter with Unknown state
-    pub fn new(c: char) -> Result<Self, &'static str> {
+    pub fn new(c: char) -> Result<Self, WordleError> {
         if !c.is_ascii_alphabetic() {
-            return Err("Character must be an ASCII letter");
+            return Err(WordleError::NonAlphabeticCharacter(c));
         }
         Ok(Self {
             character: c.to_ascii_lowercase(),
-            state: LetterState::Unknown,
+            state: LetterState::default(),
         })
     }
 
+    /// Create a new Letter without checking the character.
+    ///
+    /// # Safety
+    /// `c` must be an ASCII letter. Passing anything else breaks the invariant every
+    /// other method relies on (e.g. indexing tables by `c as u8 - b'a'`).
+    pub(crate) unsafe fn new_unchecked(c: char) -> Self {
+        debug_assert!(c.is_ascii_alphabetic());
+        Self {
+            character: c.to_ascii_lowercase(),
+            state: LetterState::default(),
+        }
+    }
+
     /// Create a new Letter with a specific state
-    pub fn with_state(c: char, state: LetterState) -> Result<Self, &'static str> {
+    pub fn with_state(c: char, state: LetterState) -> Result<Self, WordleError> {
         if !c.is_ascii_alphabetic() {
-            return Err("Character must be an ASCII letter");
+            return Err(WordleError::NonAlphabeticCharacter(c));
         }
         Ok(Self {
             character: c.to_ascii_lowercase(),
@@ -51,33 +176,193 @@ impl Letter {
     pub fn set_state(&mut self, state: LetterState) {
         self.state = state;
     }
+
+    /// Check if both letters are the same character, ignoring their states. `==`
+    /// compares the states too.
+    #[must_use]
+    pub fn same_as(&self, other: &Letter) -> bool {
+        self.character == other.character
+    }
+
+    /// Check if both letters have the same character, whatever their states
+    #[must_use]
+    pub fn overlaps(&self, other: &Letter) -> bool {
+        self.character == other.character
+    }
+}
+
+/// Displays the character of the letter, without its state.
+impl fmt::Display for Letter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.character)
+    }
+}
+
+/// Packs the ASCII character in bits 8 to 15 and the state in bits 0 to 2.
+impl From<Letter> for u16 {
+    fn from(letter: Letter) -> Self {
+        let state = match letter.state {
+            LetterState::Unknown => 0,
+            LetterState::Correct => 1,
+            LetterState::Misplaced => 2,
+            LetterState::Absent => 3,
+        };
+
+        // Letters are always ASCII, the character fits in a byte
+        (u16::from(letter.character as u8) << 8) | state
+    }
+}
+
+impl TryFrom<u16> for Letter {
+    type Error = WordleError;
+
+    fn try_from(packed: u16) -> Result<Self, Self::Error> {
+        let state = match packed & 0b111 {
+            0 => LetterState::Unknown,
+            1 => LetterState::Correct,
+            2 => LetterState::Misplaced,
+            3 => LetterState::Absent,
+            _ => return Err(WordleError::InvalidPackedLetter(packed)),
+        };
+        if packed & 0xF8 != 0 {
+            return Err(WordleError::InvalidPackedLetter(packed));
+        }
+
+        let c = char::from((packed >> 8) as u8);
+        Letter::with_state(c, state)
+    }
 }
 
 /// # `Word`
 /// Represents a word with its letters and their states.
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub struct Word {
-    letters: [Letter; 5],
+/// The game uses 5 letter words, other lengths such as `Word<4>` only support the
+/// methods that do not depend on the length, e.g. `matches_pattern`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Word<const N: usize = 5> {
+    letters: [Letter; N],
 }
 
-impl Word {
-    /// Create a new Word from a string.
-    pub fn new(word: &str) -> Result<Self, &'static str> {
-        if word.len() != 5 {
-            return Err("Word must be exactly 5 letters");
+/// # `Word5`
+/// A word of the game, the same as `Word`.
+pub type Word5 = Word<5>;
+
+/// The default word is a placeholder of Unknown 'a', meant to be overwritten.
+impl<const N: usize> Default for Word<N> {
+    fn default() -> Self {
+        Self {
+            letters: [Letter {
+                character: 'a',
+                state: LetterState::default(),
+            }; N],
+        }
+    }
+}
+
+/// Displays the lowercase letters of the word, without their states.
+impl<const N: usize> fmt::Display for Word<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.letters
+            .iter()
+            .try_for_each(|letter| write!(f, "{letter}"))
+    }
+}
+
+/// Parses a word of any length, e.g. `"crane".parse::<Word>()` or
+/// `"mini".parse::<Word<4>>()`.
+impl<const N: usize> FromStr for Word<N> {
+    type Err = WordleError;
+
+    fn from_str(word: &str) -> Result<Self, Self::Err> {
+        if word.len() != N {
+            return Err(WordleError::InvalidLength {
+                expected: N,
+                got: word.len(),
+            });
         }
 
-        let letters: Result<[Letter; 5], &'static str> = word
+        let letters: Result<[Letter; N], WordleError> = word
             .chars()
             .map(Letter::new)
             .collect::<Result<Vec<_>, _>>()?
             .try_into()
-            .map_err(|_| "Failed to convert to array");
+            .map_err(|letters: Vec<Letter>| WordleError::InvalidLength {
+                expected: N,
+                got: letters.len(),
+            });
 
         // Map the successful array into a Word struct
         letters.map(|l| Word { letters: l })
     }
+}
+
+impl<const N: usize> TryFrom<&str> for Word<N> {
+    type Error = WordleError;
+
+    fn try_from(word: &str) -> Result<Self, Self::Error> {
+        word.parse()
+    }
+}
+
+impl<const N: usize> TryFrom<String> for Word<N> {
+    type Error = WordleError;
+
+    fn try_from(word: String) -> Result<Self, Self::Error> {
+        word.parse()
+    }
+}
+
+/// Indexes the letters like `letter_at`, panicking if the position is not below the
+/// length of the word.
+impl<const N: usize> Index<usize> for Word<N> {
+    type Output = Letter;
+
+    fn index(&self, pos: usize) -> &Letter {
+        &self.letters[pos]
+    }
+}
 
+impl<const N: usize> IndexMut<usize> for Word<N> {
+    fn index_mut(&mut self, pos: usize) -> &mut Letter {
+        &mut self.letters[pos]
+    }
+}
+
+impl<const N: usize> Index<RangeFull> for Word<N> {
+    type Output = [Letter; N];
+
+    fn index(&self, _: RangeFull) -> &[Letter; N] {
+        &self.letters
+    }
+}
+
+impl<const N: usize> Index<Range<usize>> for Word<N> {
+    type Output = [Letter];
+
+    fn index(&self, range: Range<usize>) -> &[Letter] {
+        &self.letters[range]
+    }
+}
+
+/// Iterates over the letters in order, e.g. `for letter in &word`.
+impl<'a, const N: usize> IntoIterator for &'a Word<N> {
+    type Item = &'a Letter;
+    type IntoIter = std::slice::Iter<'a, Letter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.letters.iter()
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for &'a mut Word<N> {
+    type Item = &'a mut Letter;
+    type IntoIter = std::slice::IterMut<'a, Letter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.letters.iter_mut()
+    }
+}
+
+impl<const N: usize> Word<N> {
     /// # `letter_at`
     /// Returns a reference to the letter at the given position.
     ///
@@ -102,10 +387,284 @@ impl Word {
     pub fn letter_at_mut(&mut self, pos: usize) -> &mut Letter {
         &mut self.letters[pos]
     }
+
+    /// # `eq_ignoring_states`
+    /// Checks if both words have the same letters, whatever their states. `==` compares
+    /// the states too.
+    #[must_use]
+    pub fn eq_ignoring_states(&self, other: &Self) -> bool {
+        self.letters
+            .iter()
+            .zip(&other.letters)
+            .all(|(a, b)| a.same_as(b))
+    }
+}
+
+impl Word {
+    /// Create a new Word from a string, see `FromStr` for other lengths.
+    pub fn new(word: &str) -> Result<Self, WordleError> {
+        word.parse()
+    }
+
+    /// # `new_unchecked`
+    /// Create a new Word from a string without validating it, for word lists that were
+    /// already validated.
+    ///
+    /// # Safety
+    /// `word` must be exactly 5 ASCII letters, see `Letter::new_unchecked`.
+    pub(crate) unsafe fn new_unchecked(word: &str) -> Self {
+        debug_assert_eq!(word.len(), 5);
+        let mut letters = [Letter {
+            character: 'a',
+            state: LetterState::Unknown,
+        }; 5];
+        for (letter, c) in letters.iter_mut().zip(word.chars()) {
+            // SAFETY: the caller guarantees every character is an ASCII letter
+            *letter = unsafe { Letter::new_unchecked(c) };
+        }
+
+        Word { letters }
+    }
+
+    /// # `new_from_chars`
+    /// Create a new Word from an array of characters, without going through a string.
+    ///
+    /// ## Arguments
+    /// * `chars` - The 5 characters of the word.
+    ///
+    /// ## Returns
+    /// * `Result<Word, WordleError>` - The word or the first non ASCII letter found.
+    pub fn new_from_chars(chars: [char; 5]) -> Result<Self, WordleError> {
+        let mut letters = [Letter {
+            character: 'a',
+            state: LetterState::Unknown,
+        }; 5];
+        for (letter, c) in letters.iter_mut().zip(chars) {
+            *letter = Letter::new(c)?;
+        }
+
+        Ok(Word { letters })
+    }
+
+    /// # `from_pattern_code`
+    /// Creates a new Word from a string and a full pattern code.
+    ///
+    /// ## Example
+    /// `Word::from_pattern_code("crane", "GYAAU")` marks 'c' as correct, 'r' as misplaced,
+    /// 'a' and 'n' as absent and leaves 'e' unknown.
+    ///
+    /// ## Arguments
+    /// * `word` - The 5 letter word.
+    /// * `code` - Exactly 5 characters among `G`, `Y`, `A` and `U`.
+    ///
+    /// ## Returns
+    /// * `Result<Word, WordleError>` - The word with its states or an error.
+    pub fn from_pattern_code(word: &str, code: &str) -> Result<Self, WordleError> {
+        let mut pattern = Self::new(word)?;
+        pattern.apply_pattern_code(code)?;
+
+        Ok(pattern)
+    }
+
+    /// # `to_u80`
+    /// Returns the compact representation of the word, one packed `u16` per letter.
+    #[must_use]
+    pub fn to_u80(&self) -> [u16; 5] {
+        self.letters.map(u16::from)
+    }
+
+    /// # `from_u80`
+    /// Creates a word from its compact representation, the inverse of `to_u80`.
+    ///
+    /// ## Arguments
+    /// * `packed` - One packed `u16` per letter.
+    ///
+    /// ## Returns
+    /// * `Result<Word, WordleError>` - The word or an error if a letter is invalid.
+    pub fn from_u80(packed: [u16; 5]) -> Result<Self, WordleError> {
+        let mut letters = [Letter {
+            character: 'a',
+            state: LetterState::default(),
+        }; 5];
+        for (letter, packed) in letters.iter_mut().zip(packed) {
+            *letter = Letter::try_from(packed)?;
+        }
+
+        Ok(Self { letters })
+    }
+
+    /// # `revealed_count`
+    /// Counts the occurrences of a letter marked Correct or Misplaced, i.e. the copies
+    /// of the letter the answer is known to contain.
+    ///
+    /// ## Arguments
+    /// * `c` - The letter to count.
+    #[must_use]
+    pub fn revealed_count(&self, c: char) -> usize {
+        self.letters
+            .iter()
+            .filter(|l| {
+                l.character == c && matches!(l.state, LetterState::Correct | LetterState::Misplaced)
+            })
+            .count()
+    }
+
+    /// # `pattern_code`
+    /// Returns the pattern code of the states, the inverse of `from_pattern_code`.
+    #[must_use]
+    pub fn pattern_code(&self) -> String {
+        self.letters.iter().map(|l| l.state.to_code()).collect()
+    }
+
+    /// # `pattern_index`
+    /// Encodes the states as a base 3 number in `0..243`, the first letter being the
+    /// lowest digit: Absent is 0, Misplaced 1 and Correct 2. Unknown counts as Absent.
+    ///
+    /// ## Returns
+    /// * `u8` - The index of the pattern.
+    #[must_use]
+    pub fn pattern_index(&self) -> u8 {
+        self.letters.iter().rev().fold(0, |index, letter| {
+            let digit = match letter.state {
+                LetterState::Unknown | LetterState::Absent => 0,
+                LetterState::Misplaced => 1,
+                LetterState::Correct => 2,
+            };
+            index * 3 + digit
+        })
+    }
+
+    /// # `apply_pattern_code`
+    /// Sets the state of every letter from a full pattern code, e.g. `"GYAAU"`.
+    ///
+    /// ## Arguments
+    /// * `code` - Exactly 5 characters among `G`, `Y`, `A` and `U`.
+    ///
+    /// ## Returns
+    /// * `Result<(), WordleError>` - An error if the code is invalid.
+    pub fn apply_pattern_code(&mut self, code: &str) -> Result<(), WordleError> {
+        let length = code.chars().count();
+        if length != 5 {
+            return Err(WordleError::InvalidPatternLength { got: length });
+        }
+
+        self.apply_correction(code)
+    }
+
+    /// # `apply_correction`
+    /// Updates the letter states from a partial pattern code, e.g. `"GY"`.
+    /// Positions beyond the length of the code are left unchanged.
+    ///
+    /// ## Arguments
+    /// * `code` - Up to 5 characters among `G`, `Y`, `A` and `U`.
+    ///
+    /// ## Returns
+    /// * `Result<(), WordleError>` - An error if the code is invalid, in which case
+    ///   the word is left untouched.
+    pub fn apply_correction(&mut self, code: &str) -> Result<(), WordleError> {
+        let length = code.chars().count();
+        if length > 5 {
+            return Err(WordleError::PatternCodeTooLong { got: length });
+        }
+
+        let states = code
+            .chars()
+            .map(LetterState::from_code)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (letter, state) in self.letters.iter_mut().zip(states) {
+            letter.set_state(state);
+        }
+
+        Ok(())
+    }
+
+    /// # `new_random`
+    /// Picks a random answer of the word bank.
+    ///
+    /// ## Arguments
+    /// * `word_bank` - The word bank to pick from.
+    /// * `rng` - The random number generator.
+    ///
+    /// ## Returns
+    /// * `Option<Word>` - The word, or `None` if the bank has no answers.
+    #[cfg(feature = "rand")]
+    pub fn new_random(word_bank: &word_bank::WordBank, rng: &mut impl rand::Rng) -> Option<Self> {
+        word_bank
+            .random_secret(rng)
+            .and_then(|word| Self::new(word).ok())
+    }
+
+    // Patterns can only be compared when they are for the same guess
+    fn check_same_word(&self, other: &Word) -> Result<(), WordleError> {
+        if !self.eq_ignoring_states(other) {
+            return Err(WordleError::MismatchedWords(
+                self.to_string(),
+                other.to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// # `diff`
+    /// Lists the positions whose state changed between two patterns of the same guess,
+    /// e.g. to only animate those.
+    ///
+    /// ## Arguments
+    /// * `before` - The previous pattern.
+    /// * `after` - The new pattern, for the same word.
+    ///
+    /// ## Returns
+    /// * `Result<Vec<(usize, LetterState, LetterState)>, WordleError>` - The
+    ///   `(position, old_state, new_state)` of each change, or an error if the words differ.
+    pub fn diff(
+        before: &Word,
+        after: &Word,
+    ) -> Result<Vec<(usize, LetterState, LetterState)>, WordleError> {
+        before.check_same_word(after)?;
+
+        Ok(before
+            .letters
+            .iter()
+            .zip(&after.letters)
+            .enumerate()
+            .filter(|(_, (old, new))| old.state != new.state)
+            .map(|(position, (old, new))| (position, old.state, new.state))
+            .collect())
+    }
+
+    /// # `merge_constraints`
+    /// Combines two patterns of the same guess, keeping the most precise state of each
+    /// letter (Correct > Misplaced > Absent > Unknown).
+    ///
+    /// ## Arguments
+    /// * `a` - The first pattern.
+    /// * `b` - The second pattern, for the same word.
+    ///
+    /// ## Returns
+    /// * `Result<Word, WordleError>` - The merged pattern, or an error if the words differ
+    ///   or a letter is Absent in one pattern and present in the other.
+    pub fn merge_constraints(a: &Word, b: &Word) -> Result<Word, WordleError> {
+        a.check_same_word(b)?;
+
+        let mut merged = a.clone();
+        for (position, (letter, other)) in merged.letters.iter_mut().zip(&b.letters).enumerate() {
+            letter.state = match (letter.state, other.state) {
+                (LetterState::Absent, LetterState::Correct | LetterState::Misplaced)
+                | (LetterState::Correct | LetterState::Misplaced, LetterState::Absent) => {
+                    return Err(WordleError::ContradictoryStates { position });
+                }
+                (state, other) => state.max(other),
+            };
+        }
+
+        Ok(merged)
+    }
 }
 
 /// # `load_words`
-/// Loads the list of words from the JSON file.
+/// Loads the list of words from the JSON file, sorted alphabetically.
 ///
 /// ## Returns
 /// * `Vec<String>` - A vector of words loaded from the JSON file.
@@ -114,7 +673,41 @@ pub fn load_words() -> Vec<String> {
     let file = std::fs::File::open(WORDS_FILE).expect("Failed to open words file");
     let reader = std::io::BufReader::new(file);
 
-    serde_json::from_reader(reader).expect("Failed to parse words file")
+    let mut words: Vec<String> =
+        serde_json::from_reader(reader).expect("Failed to parse words file");
+    words.sort_unstable();
+
+    words
+}
+
+/// # `load_words_from_newline_separated`
+/// Loads a plain text word list with one word per line, e.g. `/usr/share/dict/words`.
+/// Words are trimmed and lowercased, those that are not only ASCII letters (proper
+/// nouns with an apostrophe, accented words...) or of another length are skipped.
+///
+/// ## Arguments
+/// * `reader` - The source of the word list.
+/// * `word_length` - The number of letters of the words to keep.
+///
+/// ## Returns
+/// * `Result<Vec<String>, WordleError>` - The words in file order, or an error if the
+///   reader fails or is not valid UTF-8.
+pub fn load_words_from_newline_separated<R: std::io::Read>(
+    reader: R,
+    word_length: usize,
+) -> Result<Vec<String>, WordleError> {
+    use std::io::BufRead;
+
+    let mut words = Vec::new();
+    for line in std::io::BufReader::new(reader).lines() {
+        let line = line.map_err(|e| WordleError::Io(e.to_string()))?;
+        let word = line.trim();
+        if word.len() == word_length && word.bytes().all(|b| b.is_ascii_alphabetic()) {
+            words.push(word.to_ascii_lowercase());
+        }
+    }
+
+    Ok(words)
 }
 
 /// # `create_pattern`
@@ -136,9 +729,40 @@ pub fn create_pattern(word: &str, states: Vec<(usize, LetterState)>) -> Word {
     pattern
 }
 
+/// Patterns reused by the tests of every module.
+#[cfg(test)]
+pub mod test_helpers {
+    use crate::Word;
+
+    /// # `pattern`
+    /// Creates a pattern from a word and its pattern code, e.g. `("crane", "GYAAU")`.
+    pub fn pattern(word: &str, code: &str) -> Word {
+        Word::from_pattern_code(word, code).unwrap()
+    }
+
+    /// # `all_correct`
+    /// Creates a pattern where every letter is Correct, as for a solved game.
+    pub fn all_correct(word: &str) -> Word {
+        pattern(word, "GGGGG")
+    }
+
+    /// # `all_absent`
+    /// Creates a pattern where every letter is Absent.
+    pub fn all_absent(word: &str) -> Word {
+        pattern(word, "AAAAA")
+    }
+
+    /// # `all_misplaced`
+    /// Creates a pattern where every letter is Misplaced.
+    pub fn all_misplaced(word: &str) -> Word {
+        pattern(word, "YYYYY")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_helpers::{all_correct, pattern};
 
     #[test]
     fn test_word_creation() {
@@ -146,6 +770,134 @@ mod tests {
         assert!(Word::new("hi").is_err());
         assert!(Word::new("toolong").is_err());
         assert!(Word::new("12345").is_err());
+        assert_eq!(
+            Word::new("toolong"),
+            Err(WordleError::InvalidLength {
+                expected: 5,
+                got: 7
+            })
+        );
+        assert_eq!(
+            Word::new("12345"),
+            Err(WordleError::NonAlphabeticCharacter('1'))
+        );
+        assert_eq!(
+            Letter::new('!'),
+            Err(WordleError::NonAlphabeticCharacter('!'))
+        );
+    }
+
+    #[test]
+    fn test_letter_same_as() {
+        let correct = Letter::with_state('e', LetterState::Correct).unwrap();
+        let unknown = Letter::new('E').unwrap();
+        assert!(correct.same_as(&unknown));
+        assert_ne!(correct, unknown);
+        assert!(!correct.same_as(&Letter::with_state('a', LetterState::Correct).unwrap()));
+    }
+
+    #[test]
+    fn test_letter_overlaps() {
+        let correct = Letter::with_state('e', LetterState::Correct).unwrap();
+        let absent = Letter::with_state('E', LetterState::Absent).unwrap();
+        assert!(correct.overlaps(&absent));
+        assert!(!correct.overlaps(&Letter::new('a').unwrap()));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Word::new("hello").unwrap()), "hello");
+        assert_eq!(pattern("CRANE", "GYAAU").to_string(), "crane");
+        assert_eq!(
+            Letter::with_state('Q', LetterState::Correct)
+                .unwrap()
+                .to_string(),
+            "q"
+        );
+        assert_eq!(LetterState::Misplaced.to_string(), "misplaced");
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("CRANE".parse::<Word>(), Ok(Word::new("crane").unwrap()));
+        assert_eq!(
+            "cr4ne".parse::<Word>(),
+            Err(WordleError::NonAlphabeticCharacter('4'))
+        );
+        assert_eq!(
+            Word5::try_from("slate"),
+            Word5::try_from("slate".to_string())
+        );
+        assert!(Word5::try_from("toolong".to_string()).is_err());
+
+        let words: Result<Vec<Word>, _> = ["crane", "slate"].iter().map(|w| w.parse()).collect();
+        assert_eq!(words.unwrap().len(), 2);
+        assert!(["crane", "hi"]
+            .iter()
+            .map(|w| w.parse::<Word>())
+            .collect::<Result<Vec<_>, _>>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_index() {
+        let mut word = pattern("crane", "GYAAU");
+        for i in 0..5 {
+            assert_eq!(&word[i], word.letter_at(i));
+        }
+
+        word[4].state = LetterState::Correct;
+        assert_eq!(word.letter_at(4).state, LetterState::Correct);
+        assert_eq!(word[..].len(), 5);
+        assert_eq!(&word[1..3], &[*word.letter_at(1), *word.letter_at(2)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_index_out_of_bounds() {
+        let word = Word::new("crane").unwrap();
+        let _ = word[5];
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_index_mut_out_of_bounds() {
+        let mut word = Word::new("crane").unwrap();
+        word[5].state = LetterState::Absent;
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_index_range_out_of_bounds() {
+        let word = Word::new("crane").unwrap();
+        let _ = &word[3..6];
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let mut word = pattern("crane", "GYAAU");
+        let characters: String = (&word).into_iter().map(|l| l.character).collect();
+        assert_eq!(characters, "crane");
+        assert_eq!(
+            (&word)
+                .into_iter()
+                .filter(|l| l.state == LetterState::Absent)
+                .count(),
+            2
+        );
+
+        for letter in &mut word {
+            letter.set_state(LetterState::Correct);
+        }
+        assert_eq!(word, all_correct("crane"));
+    }
+
+    #[test]
+    fn test_eq_ignoring_states() {
+        let pattern = pattern("crane", "GYAAU");
+        assert!(pattern.eq_ignoring_states(&Word::new("CRANE").unwrap()));
+        assert_ne!(pattern, Word::new("crane").unwrap());
+        assert!(!pattern.eq_ignoring_states(&Word::new("crone").unwrap()));
     }
 
     #[test]
@@ -159,4 +911,246 @@ mod tests {
         assert_eq!(word.letter_at(1).state, LetterState::Misplaced);
         assert_eq!(word.letter_at(2).state, LetterState::Absent);
     }
+
+    #[test]
+    fn test_apply_correction() {
+        let mut word = Word::new("crane").unwrap();
+        word.apply_correction("GY").unwrap();
+        assert_eq!(word.letter_at(0).state, LetterState::Correct);
+        assert_eq!(word.letter_at(1).state, LetterState::Misplaced);
+        assert_eq!(word.letter_at(2).state, LetterState::Unknown);
+
+        // Later corrections only override the given positions
+        word.apply_correction("uaA").unwrap();
+        assert_eq!(word.letter_at(0).state, LetterState::Unknown);
+        assert_eq!(word.letter_at(1).state, LetterState::Absent);
+        assert_eq!(word.letter_at(2).state, LetterState::Absent);
+        assert_eq!(word.letter_at(3).state, LetterState::Unknown);
+    }
+
+    #[test]
+    fn test_apply_correction_invalid() {
+        let mut word = Word::new("crane").unwrap();
+        assert_eq!(
+            word.apply_correction("GX"),
+            Err(WordleError::InvalidPatternCode('X'))
+        );
+        assert_eq!(
+            word.apply_correction("GGGGGG"),
+            Err(WordleError::PatternCodeTooLong { got: 6 })
+        );
+
+        // A failed correction does not partially apply
+        assert_eq!(word.letter_at(0).state, LetterState::Unknown);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_new_random() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let bank = word_bank::WordBank::from_words(vec!["crane".to_string(), "slate".to_string()]);
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..10 {
+            let word = Word::new_random(&bank, &mut rng).unwrap();
+            assert!(bank.answers().contains(&word.to_string()));
+        }
+        assert!(Word::new_random(&word_bank::WordBank::default(), &mut rng).is_none());
+    }
+
+    #[test]
+    fn test_letter_state_default() {
+        assert_eq!(LetterState::default(), LetterState::Unknown);
+        assert_eq!(Letter::new('a').unwrap().state, LetterState::default());
+    }
+
+    #[test]
+    fn test_word_default() {
+        let guesses: [Word; 6] = Default::default();
+        assert!(guesses
+            .iter()
+            .all(|word| *word == pattern("aaaaa", "UUUUU")));
+    }
+
+    #[test]
+    fn test_letter_state_ordering() {
+        let mut states = vec![
+            LetterState::Correct,
+            LetterState::Unknown,
+            LetterState::Misplaced,
+            LetterState::Absent,
+        ];
+        states.sort();
+        assert_eq!(
+            states,
+            vec![
+                LetterState::Unknown,
+                LetterState::Absent,
+                LetterState::Misplaced,
+                LetterState::Correct
+            ]
+        );
+        assert_eq!(
+            LetterState::Absent.max(LetterState::Misplaced),
+            LetterState::Misplaced
+        );
+    }
+
+    #[test]
+    fn test_merge_constraints() {
+        let a = pattern("crane", "GYUUA");
+        let b = pattern("crane", "UGUAA");
+        let merged = Word::merge_constraints(&a, &b).unwrap();
+        assert_eq!(merged, pattern("crane", "GGUAA"));
+
+        let absent = pattern("crane", "AUUUU");
+        assert_eq!(
+            Word::merge_constraints(&a, &absent),
+            Err(WordleError::ContradictoryStates { position: 0 })
+        );
+
+        let other = pattern("slate", "UUUUU");
+        assert_eq!(
+            Word::merge_constraints(&a, &other),
+            Err(WordleError::MismatchedWords(
+                "crane".to_string(),
+                "slate".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_packed_letters() {
+        let letter = Letter::with_state('c', LetterState::Misplaced).unwrap();
+        let packed = u16::from(letter);
+        assert_eq!(packed, (u16::from(b'c') << 8) | 2);
+        assert_eq!(Letter::try_from(packed), Ok(letter));
+
+        assert_eq!(
+            Letter::try_from((u16::from(b'c') << 8) | 5),
+            Err(WordleError::InvalidPackedLetter((u16::from(b'c') << 8) | 5))
+        );
+        assert_eq!(
+            Letter::try_from(u16::from(b'1') << 8),
+            Err(WordleError::NonAlphabeticCharacter('1'))
+        );
+
+        let word = pattern("crane", "GYAUA");
+        assert_eq!(Word::from_u80(word.to_u80()), Ok(word));
+    }
+
+    #[test]
+    fn test_pattern_code() {
+        let word = pattern("crane", "GYAAU");
+        assert_eq!(word.pattern_code(), "GYAAU");
+    }
+
+    #[test]
+    fn test_to_emoji_with_scheme() {
+        assert_eq!(
+            LetterState::Correct.to_emoji_with_scheme(ColorScheme::Default),
+            '🟩'
+        );
+        assert_eq!(
+            LetterState::Correct.to_emoji_with_scheme(ColorScheme::HighContrast),
+            '🟧'
+        );
+        assert_eq!(
+            LetterState::Misplaced.to_emoji_with_scheme(ColorScheme::HighContrast),
+            '🟦'
+        );
+        assert_eq!(
+            LetterState::Absent.to_emoji_with_scheme(ColorScheme::Accessible),
+            '⬛'
+        );
+    }
+
+    #[test]
+    fn test_pattern_index() {
+        let word = pattern("crane", "GYAAU");
+        assert_eq!(word.pattern_index(), 5);
+        let solved = all_correct("crane");
+        assert_eq!(solved.pattern_index(), 242);
+        assert_eq!(Word::new("crane").unwrap().pattern_index(), 0);
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = pattern("crane", "UUUUU");
+        let after = pattern("crane", "GUAUU");
+        assert_eq!(
+            Word::diff(&before, &after),
+            Ok(vec![
+                (0, LetterState::Unknown, LetterState::Correct),
+                (2, LetterState::Unknown, LetterState::Absent)
+            ])
+        );
+        assert_eq!(Word::diff(&after, &after), Ok(vec![]));
+
+        let other = Word::new("slate").unwrap();
+        assert!(matches!(
+            Word::diff(&before, &other),
+            Err(WordleError::MismatchedWords(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_from_pattern_code() {
+        let word = Word::from_pattern_code("crane", "GYAAU").unwrap();
+        assert_eq!(word, {
+            let mut expected = Word::new("crane").unwrap();
+            expected.letter_at_mut(0).set_state(LetterState::Correct);
+            expected.letter_at_mut(1).set_state(LetterState::Misplaced);
+            expected.letter_at_mut(2).set_state(LetterState::Absent);
+            expected.letter_at_mut(3).set_state(LetterState::Absent);
+            expected
+        });
+
+        assert_eq!(
+            Word::from_pattern_code("cran", "GYAA"),
+            Err(WordleError::InvalidLength {
+                expected: 5,
+                got: 4
+            })
+        );
+        assert_eq!(
+            Word::from_pattern_code("crane", "GYA"),
+            Err(WordleError::InvalidPatternLength { got: 3 })
+        );
+    }
+
+    #[test]
+    fn test_new_from_chars() {
+        let word = Word::new_from_chars(['C', 'r', 'a', 'n', 'e']).unwrap();
+        assert_eq!(word, Word::new("crane").unwrap());
+
+        assert_eq!(
+            Word::new_from_chars(['c', 'r', '4', 'n', 'e']),
+            Err(WordleError::NonAlphabeticCharacter('4'))
+        );
+    }
+
+    #[test]
+    fn test_new_unchecked() {
+        // SAFETY: "Crane" is made of 5 ASCII letters
+        let word = unsafe { Word::new_unchecked("Crane") };
+        assert_eq!(word, Word::new("crane").unwrap());
+    }
+
+    #[test]
+    fn test_load_words_from_newline_separated() {
+        let text = "Crane\n  slate \r\naaron's\ncafé\n\nhi\ntoolong\npious";
+        assert_eq!(
+            load_words_from_newline_separated(text.as_bytes(), 5).unwrap(),
+            vec!["crane", "slate", "pious"]
+        );
+        assert_eq!(
+            load_words_from_newline_separated(text.as_bytes(), 2).unwrap(),
+            vec!["hi"]
+        );
+        assert!(matches!(
+            load_words_from_newline_separated(&[0xff, b'\n'][..], 5),
+            Err(WordleError::Io(_))
+        ));
+    }
 }
@@ -0,0 +1,217 @@
+//! Module containing the table of every response between the words of a word bank,
+//! computed once to make the solvers' lookups constant time.
+
+use crate::{
+    game_logic::{compute_response, entropy_of_counts},
+    word_bank::WordBank,
+    LetterState, Word, WordleError,
+};
+
+/// Pattern stored for a pair involving a word that is not a valid 5 letter word.
+pub const INVALID_PATTERN: u8 = u8::MAX;
+
+/// # `decode_pattern_index`
+/// Rebuilds the response encoded by `Word::pattern_index`, e.g. to display a
+/// pattern stored in a `PatternTable`.
+///
+/// ## Arguments
+/// * `index` - The pattern index, in `0..243`.
+/// * `guess_word` - The guess the response is for.
+///
+/// ## Returns
+/// * `Result<Word, WordleError>` - The guess with the states of the response, or an
+///   error if the index or the word is invalid. Unknown states decode as Absent.
+pub fn decode_pattern_index(index: u8, guess_word: &str) -> Result<Word, WordleError> {
+    if index >= 243 {
+        return Err(WordleError::InvalidPatternIndex(index));
+    }
+    let mut word = Word::new(guess_word)?;
+
+    // The first letter is the lowest base 3 digit
+    let mut rest = index;
+    for i in 0..5 {
+        let state = match rest % 3 {
+            0 => LetterState::Absent,
+            1 => LetterState::Misplaced,
+            _ => LetterState::Correct,
+        };
+        word.letter_at_mut(i).set_state(state);
+        rest /= 3;
+    }
+
+    Ok(word)
+}
+
+/// # `PatternTable`
+/// The response of every pair of words of a word bank, encoded with
+/// `Word::pattern_index`. Words are indexed in the order of `WordBank::words`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternTable {
+    data: Vec<u8>,
+    n: usize,
+}
+
+impl PatternTable {
+    /// # `build`
+    /// Computes the response of every guess against every secret of the word bank.
+    /// This takes `N²` time and bytes for `N` words.
+    ///
+    /// ## Arguments
+    /// * `word_bank` - The words to use as guesses and secrets.
+    ///
+    /// ## Returns
+    /// * `PatternTable` - The table of the responses.
+    #[must_use]
+    pub fn build(word_bank: &WordBank) -> Self {
+        let words: Vec<Option<Word>> = word_bank
+            .answers()
+            .iter()
+            .map(|word| Word::new(word).ok())
+            .collect();
+        let n = words.len();
+
+        let mut data = Vec::with_capacity(n * n);
+        for guess in &words {
+            for secret in &words {
+                data.push(match (guess, secret) {
+                    (Some(guess), Some(secret)) => compute_response(guess, secret).pattern_index(),
+                    _ => INVALID_PATTERN,
+                });
+            }
+        }
+
+        Self { data, n }
+    }
+
+    /// # `get`
+    /// Returns the response of a guess against a secret.
+    ///
+    /// ## Arguments
+    /// * `guess_idx` - The index of the guess in the word bank.
+    /// * `secret_idx` - The index of the secret in the word bank.
+    ///
+    /// ## Returns
+    /// * `u8` - The pattern index of the response, `INVALID_PATTERN` if a word is invalid.
+    ///
+    /// ## Panics
+    /// If an index is out of the table.
+    #[must_use]
+    pub fn get(&self, guess_idx: usize, secret_idx: usize) -> u8 {
+        assert!(secret_idx < self.n, "secret index out of the table");
+        self.data[guess_idx * self.n + secret_idx]
+    }
+
+    /// # `len`
+    /// Returns the number of words of the table.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// # `is_empty`
+    /// Returns whether the table has no words.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// # `entropy`
+    /// Returns the information a guess gives on average, in bits, the same as
+    /// `compute_entropy` without computing any response.
+    ///
+    /// ## Arguments
+    /// * `guess_idx` - The index of the guess in the word bank.
+    /// * `candidates` - The indices of the words the secret can still be.
+    ///
+    /// ## Returns
+    /// * `f64` - The entropy of the responses, higher is better.
+    #[must_use]
+    pub fn entropy(&self, guess_idx: usize, candidates: &[usize]) -> f64 {
+        let mut counts = [0usize; 243];
+        for &secret_idx in candidates {
+            let pattern = self.get(guess_idx, secret_idx);
+            if pattern != INVALID_PATTERN {
+                counts[pattern as usize] += 1;
+            }
+        }
+
+        entropy_of_counts(&counts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_logic::compute_entropy;
+
+    fn bank() -> WordBank {
+        WordBank::from_words(
+            ["crate", "grate", "irate", "trace", "slate"]
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_build_matches_compute_response() {
+        let bank = bank();
+        let table = PatternTable::build(&bank);
+        assert_eq!(table.len(), 5);
+
+        for (i, guess) in bank.answers().iter().enumerate() {
+            for (j, secret) in bank.answers().iter().enumerate() {
+                let response =
+                    compute_response(&Word::new(guess).unwrap(), &Word::new(secret).unwrap());
+                assert_eq!(table.get(i, j), response.pattern_index());
+            }
+        }
+        assert_eq!(table.get(0, 0), 242);
+    }
+
+    #[test]
+    fn test_entropy_matches_compute_entropy() {
+        let bank = bank();
+        let table = PatternTable::build(&bank);
+        let candidates: Vec<usize> = (0..table.len()).collect();
+
+        for (i, guess) in bank.answers().iter().enumerate() {
+            let expected = compute_entropy(&Word::new(guess).unwrap(), bank.answers());
+            assert!((table.entropy(i, &candidates) - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_decode_pattern_index() {
+        for index in 0..243 {
+            let word = decode_pattern_index(index, "crate").unwrap();
+            assert_eq!(word.pattern_index(), index);
+        }
+        assert_eq!(
+            decode_pattern_index(242, "CRATE"),
+            Ok(Word::from_pattern_code("crate", "GGGGG").unwrap())
+        );
+        assert_eq!(
+            decode_pattern_index(1 + 2 * 3, "crate"),
+            Ok(Word::from_pattern_code("crate", "YGAAA").unwrap())
+        );
+
+        assert_eq!(
+            decode_pattern_index(INVALID_PATTERN, "crate"),
+            Err(WordleError::InvalidPatternIndex(INVALID_PATTERN))
+        );
+        assert_eq!(
+            decode_pattern_index(0, "cr4te"),
+            Err(WordleError::NonAlphabeticCharacter('4'))
+        );
+    }
+
+    #[test]
+    fn test_invalid_words() {
+        let bank = WordBank::from_words(vec!["crate".to_string(), "toolong".to_string()]);
+        let table = PatternTable::build(&bank);
+        assert_eq!(table.get(0, 1), INVALID_PATTERN);
+        assert_eq!(table.get(1, 0), INVALID_PATTERN);
+        assert!(table.entropy(0, &[1]).abs() < f64::EPSILON);
+    }
+}
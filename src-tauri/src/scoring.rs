@@ -0,0 +1,524 @@
+//! Module containing the scoring functions used to rank candidate words.
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::{game_logic::compute_entropy, word_bank::WordBank, Word, WordleError};
+
+/// # `LetterFrequency`
+/// How often each letter (`a` to `z`) appears in a list of words, overall and at each position.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LetterFrequency {
+    pub overall: [u32; 26],
+    pub positions: [[u32; 26]; 5],
+}
+
+impl LetterFrequency {
+    /// # `as_json`
+    /// Returns the frequencies keyed by letter, as used by the frontend:
+    /// `{ "a": { "overall": 1234, "positions": [12, 34, 56, 78, 90] }, ... }`.
+    #[must_use]
+    pub fn as_json(&self) -> Value {
+        let letters: Map<String, Value> = ('a'..='z')
+            .zip(self.overall)
+            .enumerate()
+            .map(|(i, (c, overall))| {
+                let positions: Vec<u32> = self.positions.iter().map(|row| row[i]).collect();
+                (
+                    c.to_string(),
+                    json!({ "overall": overall, "positions": positions }),
+                )
+            })
+            .collect();
+
+        Value::Object(letters)
+    }
+
+    /// # `top_letters`
+    /// Returns the most frequent letters overall.
+    ///
+    /// ## Arguments
+    /// * `n` - The maximum number of letters to return.
+    ///
+    /// ## Returns
+    /// * `Vec<(char, u32)>` - At most `n` letters that appear, with their count, most
+    ///   frequent first, ties in alphabetical order.
+    #[must_use]
+    pub fn top_letters(&self, n: usize) -> Vec<(char, u32)> {
+        top_counts(&self.overall, n)
+    }
+
+    /// # `top_letters_at_position`
+    /// Returns the most frequent letters at a position.
+    ///
+    /// ## Arguments
+    /// * `pos` - The position, from 0 to 4.
+    /// * `n` - The maximum number of letters to return.
+    ///
+    /// ## Returns
+    /// * `Vec<(char, u32)>` - Same as `top_letters`, empty if the position is invalid.
+    #[must_use]
+    pub fn top_letters_at_position(&self, pos: usize, n: usize) -> Vec<(char, u32)> {
+        self.positions
+            .get(pos)
+            .map(|row| top_counts(row, n))
+            .unwrap_or_default()
+    }
+}
+
+// The `n` letters with the highest non-zero count, the sort is stable to keep ties alphabetical
+fn top_counts(counts: &[u32; 26], n: usize) -> Vec<(char, u32)> {
+    let mut letters: Vec<(char, u32)> = ('a'..='z')
+        .zip(counts.iter().copied())
+        .filter(|&(_, count)| count > 0)
+        .collect();
+    letters.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    letters.truncate(n);
+
+    letters
+}
+
+/// # `letter_frequency_table`
+/// Counts every occurrence of each letter in the words, overall and at each position.
+///
+/// ## Arguments
+/// * `words` - The list of words to count in.
+///
+/// ## Returns
+/// * `LetterFrequency` - The occurrence counts.
+#[must_use]
+pub fn letter_frequency_table(words: &[String]) -> LetterFrequency {
+    let mut frequency = LetterFrequency::default();
+    for word in words {
+        let Ok(word) = Word::new(word) else {
+            continue;
+        };
+        for (pos, row) in frequency.positions.iter_mut().enumerate() {
+            let index = letter_index(word.letter_at(pos).character);
+            row[index] += 1;
+            frequency.overall[index] += 1;
+        }
+    }
+
+    frequency
+}
+
+/// # `letter_presence_counts`
+/// Counts, for each letter, how many candidates contain it at least once.
+///
+/// ## Arguments
+/// * `candidates` - The list of remaining candidates.
+///
+/// ## Returns
+/// * `HashMap<char, u32>` - The number of candidates containing each letter.
+#[must_use]
+pub fn letter_presence_counts(candidates: &[String]) -> HashMap<char, u32> {
+    let mut frequencies: HashMap<char, u32> = HashMap::new();
+    for candidate in candidates {
+        let mut seen = Vec::with_capacity(5);
+        for c in candidate.chars() {
+            if !seen.contains(&c) {
+                seen.push(c);
+                *frequencies.entry(c).or_insert(0) += 1;
+            }
+        }
+    }
+
+    frequencies
+}
+
+/// # `score_with_presence_counts`
+/// Scores a word with precomputed `letter_presence_counts`, see `score_by_letter_frequency`.
+/// Useful to score many words against the same candidates.
+///
+/// ## Arguments
+/// * `word` - The word to score.
+/// * `frequencies` - The presence counts of the candidates.
+///
+/// ## Returns
+/// * `f64` - The score of the word, higher is better.
+#[must_use]
+pub fn score_with_presence_counts(word: &Word, frequencies: &HashMap<char, u32>) -> f64 {
+    let mut seen = Vec::with_capacity(5);
+    let mut score = 0;
+    for i in 0..5 {
+        let c = word.letter_at(i).character;
+        if !seen.contains(&c) {
+            seen.push(c);
+            score += frequencies.get(&c).copied().unwrap_or(0);
+        }
+    }
+
+    f64::from(score)
+}
+
+/// # `score_by_letter_frequency`
+/// Scores a word by how many candidates contain each of its distinct letters.
+/// Repeated letters only count once since they reveal less information.
+///
+/// ## Arguments
+/// * `word` - The word to score.
+/// * `candidates` - The list of remaining candidates.
+///
+/// ## Returns
+/// * `f64` - The score of the word, higher is better.
+#[must_use]
+pub fn score_by_letter_frequency(word: &Word, candidates: &[String]) -> f64 {
+    score_with_presence_counts(word, &letter_presence_counts(candidates))
+}
+
+/// # `score_by_entropy`
+/// Scores a word by the information its response gives on average, in bits.
+///
+/// ## Arguments
+/// * `word` - The word to score.
+/// * `candidates` - The list of remaining candidates.
+///
+/// ## Returns
+/// * `f64` - The score of the word, higher is better.
+#[must_use]
+pub fn score_by_entropy(word: &Word, candidates: &[String]) -> f64 {
+    compute_entropy(word, candidates)
+}
+
+/// # `score_by_elimination`
+/// Scores a word by the fraction of candidates its response eliminates on average.
+/// Candidates are bucketed by which letters of the word are in place and which are
+/// contained, an approximation of the response that is faster than `score_by_entropy`.
+///
+/// ## Arguments
+/// * `word` - The word to score.
+/// * `candidates` - The list of remaining candidates.
+///
+/// ## Returns
+/// * `f64` - The expected fraction of candidates eliminated, higher is better.
+#[must_use]
+pub fn score_by_elimination(word: &Word, candidates: &[String]) -> f64 {
+    if candidates.is_empty() {
+        return 0.0;
+    }
+
+    let letters: Vec<u8> = (0..5).map(|i| word.letter_at(i).character as u8).collect();
+    let mut buckets: HashMap<u16, usize> = HashMap::new();
+    for candidate in candidates {
+        let bytes = candidate.as_bytes();
+        // Bits 0-4 are the letters in place, bits 5-9 the letters contained
+        let key = letters.iter().enumerate().fold(0u16, |key, (i, &letter)| {
+            let in_place = u16::from(bytes.get(i) == Some(&letter)) << i;
+            let contained = u16::from(bytes.contains(&letter)) << (i + 5);
+            key | in_place | contained
+        });
+        *buckets.entry(key).or_default() += 1;
+    }
+
+    let squares: usize = buckets.values().map(|size| size.pow(2)).sum();
+    // Candidate counts are far below 2^52, the conversions are exact
+    let total = candidates.len() as f64;
+    1.0 - squares as f64 / total / total
+}
+
+/// # `score_opening_words`
+/// Scores every allowed guess with `score_by_entropy` against every answer. The best
+/// opener does not depend on any guess, but this is quadratic in the size of the bank:
+/// compute it once and keep it with `save_opening_scores`.
+///
+/// ## Arguments
+/// * `word_bank` - The allowed guesses to score and the answers to score against.
+///
+/// ## Returns
+/// * `Vec<(String, f64)>` - Every guess with its score, best first.
+#[must_use]
+pub fn score_opening_words(word_bank: &WordBank) -> Vec<(String, f64)> {
+    let mut scores: Vec<(String, f64)> = word_bank
+        .allowed
+        .iter()
+        .filter_map(|word| {
+            let guess = Word::new(word).ok()?;
+            Some((word.clone(), score_by_entropy(&guess, word_bank.answers())))
+        })
+        .collect();
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    scores
+}
+
+/// # `save_opening_scores`
+/// Saves the opening scores to a JSON file, creating its parent directory if needed.
+///
+/// ## Arguments
+/// * `scores` - The scores returned by `score_opening_words`.
+/// * `path` - The path of the JSON file.
+///
+/// ## Returns
+/// * `Result<(), WordleError>` - An error if the file could not be written.
+pub fn save_opening_scores(scores: &[(String, f64)], path: &Path) -> Result<(), WordleError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| WordleError::Io(e.to_string()))?;
+    }
+
+    // One score per allowed guess, thousands of tiny writes without the buffer
+    let file = File::create(path).map_err(|e| WordleError::Io(e.to_string()))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, scores).map_err(|e| WordleError::Io(e.to_string()))?;
+    // Flush explicitly, a failed write would go unnoticed when the writer is dropped
+    writer.flush().map_err(|e| WordleError::Io(e.to_string()))
+}
+
+/// # `load_opening_scores`
+/// Loads the opening scores saved by `save_opening_scores`.
+///
+/// ## Arguments
+/// * `path` - The path of the JSON file.
+///
+/// ## Returns
+/// * `Result<Vec<(String, f64)>, WordleError>` - The scores, best first, or an error if
+///   the file is missing or invalid.
+pub fn load_opening_scores(path: &Path) -> Result<Vec<(String, f64)>, WordleError> {
+    let file = File::open(path).map_err(|e| WordleError::Io(e.to_string()))?;
+    serde_json::from_reader(BufReader::new(file)).map_err(|e| WordleError::Io(e.to_string()))
+}
+
+/// # `compute_bigram_frequency`
+/// Counts how often each pair of adjacent letters appears in the words.
+///
+/// ## Arguments
+/// * `words` - The list of words to count in.
+///
+/// ## Returns
+/// * `HashMap<(char, char), u32>` - The number of occurrences of each bigram.
+#[must_use]
+pub fn compute_bigram_frequency(words: &[String]) -> HashMap<(char, char), u32> {
+    let mut bigrams: HashMap<(char, char), u32> = HashMap::new();
+    for word in words {
+        let chars: Vec<char> = word.chars().collect();
+        for pair in chars.windows(2) {
+            *bigrams.entry((pair[0], pair[1])).or_insert(0) += 1;
+        }
+    }
+
+    bigrams
+}
+
+/// # `score_by_bigrams`
+/// Scores a word by summing the frequencies of its 4 adjacent letter pairs.
+///
+/// ## Arguments
+/// * `word` - The word to score.
+/// * `bigrams` - The bigram frequencies, see `compute_bigram_frequency`.
+///
+/// ## Returns
+/// * `f64` - The score of the word, higher is better.
+#[must_use]
+pub fn score_by_bigrams(word: &Word, bigrams: &HashMap<(char, char), u32>) -> f64 {
+    (0..4)
+        .map(|i| {
+            let pair = (word.letter_at(i).character, word.letter_at(i + 1).character);
+            f64::from(bigrams.get(&pair).copied().unwrap_or(0))
+        })
+        .sum()
+}
+
+/// Weight of each letter (`a` to `z`) at each of the 5 positions.
+pub type PositionWeights = [[f64; 26]; 5];
+
+/// # `compute_position_weights`
+/// Computes, for each position, the share of words having each letter there.
+///
+/// ## Arguments
+/// * `words` - The list of words to count in.
+///
+/// ## Returns
+/// * `PositionWeights` - The frequency of each letter at each position, between 0 and 1.
+#[must_use]
+pub fn compute_position_weights(words: &[String]) -> PositionWeights {
+    let mut counts = [[0_u32; 26]; 5];
+    let mut total = 0_u32;
+    for word in words {
+        let Ok(word) = Word::new(word) else {
+            continue;
+        };
+        total += 1;
+        for (pos, row) in counts.iter_mut().enumerate() {
+            row[letter_index(word.letter_at(pos).character)] += 1;
+        }
+    }
+
+    let mut table = [[0.0; 26]; 5];
+    if total > 0 {
+        for (weights, row) in table.iter_mut().zip(counts) {
+            for (weight, count) in weights.iter_mut().zip(row) {
+                *weight = f64::from(count) / f64::from(total);
+            }
+        }
+    }
+
+    table
+}
+
+// Letters of a `Word` are always lowercase ASCII
+fn letter_index(c: char) -> usize {
+    (c as u8 - b'a') as usize
+}
+
+impl Word {
+    /// # `score_position_weighted`
+    /// Scores the word by summing the weight of each letter at its position.
+    ///
+    /// ## Arguments
+    /// * `table` - The weights, e.g. from `compute_position_weights`.
+    ///
+    /// ## Returns
+    /// * `f64` - The score of the word, higher is better.
+    #[must_use]
+    pub fn score_position_weighted(&self, table: &PositionWeights) -> f64 {
+        table
+            .iter()
+            .enumerate()
+            .map(|(pos, weights)| weights[letter_index(self.letter_at(pos).character)])
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_by_letter_frequency() {
+        let candidates = vec![
+            "paint".to_string(),
+            "taint".to_string(),
+            "saint".to_string(),
+        ];
+
+        // 'a', 'i', 'n', 't' appear in every candidate, 's' in one
+        let word = Word::new("saint").unwrap();
+        assert!((score_by_letter_frequency(&word, &candidates) - 13.0).abs() < f64::EPSILON);
+
+        // Repeated letters only count once
+        let word = Word::new("tatty").unwrap();
+        assert!((score_by_letter_frequency(&word, &candidates) - 6.0).abs() < f64::EPSILON);
+
+        // No candidates means no information
+        assert!(score_by_letter_frequency(&word, &[]).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_score_by_elimination() {
+        let candidates = vec![
+            "paint".to_string(),
+            "taint".to_string(),
+            "saint".to_string(),
+        ];
+
+        // 'p' and 's' tell every candidate apart
+        let word = Word::new("ptsxx").unwrap();
+        assert!((score_by_elimination(&word, &candidates) - 2.0 / 3.0).abs() < 1e-9);
+
+        // Every candidate contains "aint" in place, nothing is eliminated
+        let word = Word::new("xaint").unwrap();
+        assert!(score_by_elimination(&word, &candidates).abs() < f64::EPSILON);
+
+        assert!(score_by_elimination(&word, &[]).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_score_opening_words() {
+        let bank = WordBank::from_lists(
+            vec![
+                "crate".to_string(),
+                "grate".to_string(),
+                "irate".to_string(),
+                "trace".to_string(),
+            ],
+            vec!["mummy".to_string()],
+        );
+        let scores = score_opening_words(&bank);
+        assert_eq!(scores.len(), 5);
+        assert!(scores.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        // "mummy" shares no letter with the answers, it tells nothing
+        assert_eq!(scores.last(), Some(&("mummy".to_string(), 0.0)));
+
+        let path = std::env::temp_dir().join(format!("wordle_openers_{}.json", std::process::id()));
+        save_opening_scores(&scores, &path).unwrap();
+        assert_eq!(load_opening_scores(&path).unwrap(), scores);
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(load_opening_scores(&path).is_err());
+    }
+
+    #[test]
+    fn test_compute_bigram_frequency() {
+        let words = vec!["shape".to_string(), "sharp".to_string()];
+        let bigrams = compute_bigram_frequency(&words);
+
+        assert_eq!(bigrams.get(&('s', 'h')), Some(&2));
+        assert_eq!(bigrams.get(&('h', 'a')), Some(&2));
+        assert_eq!(bigrams.get(&('p', 'e')), Some(&1));
+        assert_eq!(bigrams.get(&('e', 's')), None);
+        assert_eq!(bigrams.values().sum::<u32>(), 8);
+    }
+
+    #[test]
+    fn test_score_by_bigrams() {
+        let words = vec!["shape".to_string(), "sharp".to_string()];
+        let bigrams = compute_bigram_frequency(&words);
+
+        // sh (2) + ha (2) + ar (1) + re (0)
+        let word = Word::new("share").unwrap();
+        assert!((score_by_bigrams(&word, &bigrams) - 5.0).abs() < f64::EPSILON);
+
+        let word = Word::new("pious").unwrap();
+        assert!(score_by_bigrams(&word, &bigrams).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_score_position_weighted() {
+        let words = vec!["shape".to_string(), "sharp".to_string()];
+        let table = compute_position_weights(&words);
+        assert!((table[0][letter_index('s')] - 1.0).abs() < f64::EPSILON);
+        assert!((table[4][letter_index('e')] - 0.5).abs() < f64::EPSILON);
+
+        // s (1) + h (1) + a (1) + r (0.5) + e (0.5)
+        let word = Word::new("share").unwrap();
+        assert!((word.score_position_weighted(&table) - 4.0).abs() < f64::EPSILON);
+
+        let word = Word::new("pious").unwrap();
+        assert!(word.score_position_weighted(&table).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_letter_frequency_table() {
+        let words = vec!["shape".to_string(), "sheep".to_string()];
+        let frequency = letter_frequency_table(&words);
+        assert_eq!(frequency.overall[letter_index('e')], 3);
+        assert_eq!(frequency.positions[2][letter_index('e')], 1);
+
+        let json = frequency.as_json();
+        assert_eq!(json["e"]["overall"], 3);
+        assert_eq!(json["e"]["positions"], json!([0, 0, 1, 1, 1]));
+        assert_eq!(json["z"]["overall"], 0);
+        assert_eq!(json.as_object().unwrap().len(), 26);
+    }
+
+    #[test]
+    fn test_top_letters() {
+        let words = vec!["shape".to_string(), "sheep".to_string()];
+        let frequency = letter_frequency_table(&words);
+
+        assert_eq!(frequency.top_letters(3), vec![('e', 3), ('h', 2), ('p', 2)]);
+        assert_eq!(frequency.top_letters(100).len(), 5);
+        assert_eq!(
+            frequency.top_letters_at_position(2, 5),
+            vec![('a', 1), ('e', 1)]
+        );
+        assert!(frequency.top_letters_at_position(5, 5).is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+//! Module containing the statistics tracked across games.
+
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{game_state::GamePhase, WordleError};
+
+/// # `WordleStats`
+/// The outcomes of every finished game, as shown in the statistics modal.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WordleStats {
+    pub games_played: u32,
+    pub games_won: u32,
+    pub current_streak: u32,
+    pub max_streak: u32,
+    /// Games won in 1 to 6 guesses at indices 0 to 5, games lost at index 6.
+    pub guess_distribution: [u32; 7],
+}
+
+impl WordleStats {
+    /// # `update`
+    /// Records the outcome of a game. Games still in progress are ignored.
+    ///
+    /// ## Arguments
+    /// * `result` - The final phase of the game.
+    /// * `guesses_used` - The number of guesses made.
+    pub fn update(&mut self, result: GamePhase, guesses_used: usize) {
+        match result {
+            GamePhase::InProgress => return,
+            GamePhase::Won => {
+                self.games_won += 1;
+                self.current_streak += 1;
+                self.max_streak = self.max_streak.max(self.current_streak);
+                self.guess_distribution[guesses_used.clamp(1, 6) - 1] += 1;
+            }
+            GamePhase::Lost => {
+                self.current_streak = 0;
+                self.guess_distribution[6] += 1;
+            }
+        }
+        self.games_played += 1;
+    }
+
+    /// # `win_percentage`
+    /// Returns the share of games won, between 0 and 100.
+    #[must_use]
+    pub fn win_percentage(&self) -> f64 {
+        if self.games_played == 0 {
+            return 0.0;
+        }
+
+        f64::from(self.games_won) * 100.0 / f64::from(self.games_played)
+    }
+
+    /// # `load`
+    /// Loads the statistics from a JSON file, a missing file means no games played yet.
+    ///
+    /// ## Arguments
+    /// * `path` - The path of the JSON file.
+    ///
+    /// ## Returns
+    /// * `Result<WordleStats, WordleError>` - The statistics or an error if the file is invalid.
+    pub fn load(path: &Path) -> Result<Self, WordleError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let file = File::open(path).map_err(|e| WordleError::Io(e.to_string()))?;
+        serde_json::from_reader(BufReader::new(file)).map_err(|e| WordleError::Io(e.to_string()))
+    }
+
+    /// # `save`
+    /// Saves the statistics to a JSON file, creating its parent directory if needed.
+    ///
+    /// ## Arguments
+    /// * `path` - The path of the JSON file.
+    ///
+    /// ## Returns
+    /// * `Result<(), WordleError>` - An error if the file could not be written.
+    pub fn save(&self, path: &Path) -> Result<(), WordleError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| WordleError::Io(e.to_string()))?;
+        }
+
+        // serde_json writes token by token, buffer them into a few system calls
+        let file = File::create(path).map_err(|e| WordleError::Io(e.to_string()))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer(&mut writer, self).map_err(|e| WordleError::Io(e.to_string()))?;
+        // Dropping the writer flushes it too, but would silently ignore a failed write
+        writer.flush().map_err(|e| WordleError::Io(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update() {
+        let mut stats = WordleStats::default();
+        stats.update(GamePhase::Won, 3);
+        stats.update(GamePhase::Won, 4);
+        stats.update(GamePhase::InProgress, 2);
+
+        assert_eq!(stats.games_played, 2);
+        assert_eq!(stats.current_streak, 2);
+        assert_eq!(stats.guess_distribution, [0, 0, 1, 1, 0, 0, 0]);
+
+        stats.update(GamePhase::Lost, 6);
+        stats.update(GamePhase::Won, 1);
+
+        assert_eq!(stats.games_played, 4);
+        assert_eq!(stats.games_won, 3);
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.max_streak, 2);
+        assert_eq!(stats.guess_distribution, [1, 0, 1, 1, 0, 0, 1]);
+        assert!((stats.win_percentage() - 75.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!("wordle_stats_{}.json", std::process::id()));
+
+        let mut stats = WordleStats::default();
+        stats.update(GamePhase::Won, 2);
+        stats.save(&path).unwrap();
+        assert_eq!(WordleStats::load(&path).unwrap(), stats);
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(WordleStats::load(&path).unwrap(), WordleStats::default());
+    }
+}
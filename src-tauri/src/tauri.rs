@@ -1,15 +1,604 @@
-use crate::{data::WordData, game_logic::filter_word_list};
+#[cfg(not(feature = "daily"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use tauri::{Emitter, Manager};
+
+#[cfg(feature = "daily")]
+use crate::daily::{daily_word, todays_word};
+#[cfg(feature = "fuzzy")]
+use crate::fuzzy::fuzzy_lookup;
+use crate::{
+    config::{ColorScheme, WordleConfig, WORD_LENGTH},
+    data::{
+        keyboard_to_data, word_data_json_schema, word_to_data, EvaluationProgress, FilterOptions,
+        FilteredWords, GuessAnalysis, GuessResult, WordData, WordValidity,
+    },
+    game_logic::{
+        analyze_guess, debug_filter_word_list, filter_word_list_of_length,
+        filter_word_list_with_options, hard_mode_validity, pattern_statistics, top_candidate_list,
+        worst_case_remaining, FilterTrace,
+    },
+    game_state::{GameMode, GamePhase, GameState, PostGameSummary},
+    scoring::{
+        letter_frequency_table, load_opening_scores, save_opening_scores, score_opening_words,
+    },
+    solver::{self, evaluate_strategy, FrequencySolver, GameSolver, StrategyStats},
+    stats::WordleStats,
+    word_bank::{is_valid_answer, WordBank},
+    WordleError,
+};
+#[cfg(feature = "rand")]
+use crate::{
+    scoring::{letter_presence_counts, score_with_presence_counts},
+    word_bank::sample_n_weighted,
+    Word,
+};
+
+const STATS_FILE: &str = "stats.json";
+#[cfg(feature = "config")]
+const CONFIG_FILE: &str = "config.toml";
+const OPENING_SCORES_FILE: &str = "opening_scores.json";
+const EVALUATION_PROGRESS_EVENT: &str = "evaluation-progress";
+
+/// # `StatsStore`
+/// The statistics together with the file they are persisted to.
+pub struct StatsStore {
+    path: PathBuf,
+    stats: WordleStats,
+}
+
+impl StatsStore {
+    /// # `load`
+    /// Loads the statistics from the given file, starting fresh if it is unreadable.
+    ///
+    /// ## Arguments
+    /// * `path` - The path of the JSON file.
+    #[must_use]
+    pub fn load(path: PathBuf) -> Self {
+        let stats = WordleStats::load(&path).unwrap_or_default();
+        Self { path, stats }
+    }
+
+    fn save(&self) -> Result<(), String> {
+        self.stats.save(&self.path).map_err(|e| e.to_string())
+    }
+}
+
+/// # `GameSessions`
+/// The games in progress, identified by the session id returned by `new_game`.
+#[derive(Debug, Default)]
+pub struct GameSessions {
+    games: HashMap<u64, GameState>,
+    next_id: u64,
+}
+
+impl GameSessions {
+    /// # `start`
+    /// Registers a new game.
+    ///
+    /// ## Arguments
+    /// * `game` - The game to register.
+    ///
+    /// ## Returns
+    /// * `u64` - The session id of the game.
+    pub fn start(&mut self, game: GameState) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.games.insert(id, game);
+
+        id
+    }
+
+    /// # `get`
+    /// Returns the game of a session.
+    ///
+    /// ## Arguments
+    /// * `session_id` - The session id of the game.
+    ///
+    /// ## Returns
+    /// * `Result<&GameState, String>` - The game or an error if the session does not exist.
+    pub fn get(&self, session_id: u64) -> Result<&GameState, String> {
+        self.games
+            .get(&session_id)
+            .ok_or_else(|| format!("Unknown game session {session_id}"))
+    }
+
+    /// # `get_mut`
+    /// Returns the game of a session, mutably.
+    ///
+    /// ## Arguments
+    /// * `session_id` - The session id of the game.
+    ///
+    /// ## Returns
+    /// * `Result<&mut GameState, String>` - The game or an error if the session does not exist.
+    pub fn get_mut(&mut self, session_id: u64) -> Result<&mut GameState, String> {
+        self.games
+            .get_mut(&session_id)
+            .ok_or_else(|| format!("Unknown game session {session_id}"))
+    }
+}
+
+/// # `WordleApp`
+/// All the state of the application, managed by Tauri as a single state.
+pub struct WordleApp {
+    word_bank: Arc<WordBank>,
+    game_states: Arc<Mutex<GameSessions>>,
+    config: Arc<WordleConfig>,
+    stats: Arc<Mutex<StatsStore>>,
+    opening_scores_file: PathBuf, // Caches the scores of every opener, computed on first use
+}
+
+impl WordleApp {
+    /// # `new`
+    /// Loads the word bank and the statistics and starts without any game.
+    ///
+    /// ## Arguments
+    /// * `config` - The settings of the application.
+    /// * `data_dir` - The directory the statistics and the caches are persisted to.
+    ///
+    /// ## Returns
+    /// * `Result<WordleApp, WordleError>` - The state or an error if the word bank
+    ///   cannot be loaded.
+    pub fn new(config: WordleConfig, data_dir: &Path) -> Result<Self, WordleError> {
+        Ok(Self {
+            word_bank: Arc::new(WordBank::load_file(&config.words_file)?),
+            game_states: Arc::new(Mutex::new(GameSessions::default())),
+            config: Arc::new(config),
+            stats: Arc::new(Mutex::new(StatsStore::load(data_dir.join(STATS_FILE)))),
+            opening_scores_file: data_dir.join(OPENING_SCORES_FILE),
+        })
+    }
+
+    fn game_states(&self) -> Result<MutexGuard<'_, GameSessions>, String> {
+        self.game_states.lock().map_err(|e| e.to_string())
+    }
+
+    fn stats(&self) -> Result<MutexGuard<'_, StatsStore>, String> {
+        self.stats.lock().map_err(|e| e.to_string())
+    }
+}
+
+#[tauri::command]
+pub fn filter_word_list_command(
+    patterns: Vec<WordData>,
+    word_length: Option<usize>,
+    app: tauri::State<WordleApp>,
+) -> Result<Vec<String>, String> {
+    filter_word_list_of_length(
+        &app.word_bank,
+        &patterns,
+        word_length.unwrap_or(WORD_LENGTH),
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn filter_word_list_with_options_command(
+    patterns: Vec<WordData>,
+    options: FilterOptions,
+    app: tauri::State<WordleApp>,
+) -> Result<FilteredWords, String> {
+    filter_word_list_with_options(&app.word_bank, &patterns, options).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn debug_filter_word_list_command(
+    patterns: Vec<WordData>,
+    app: tauri::State<WordleApp>,
+) -> Result<FilterTrace, String> {
+    debug_filter_word_list(&app.word_bank, &patterns).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_top_candidates(
+    patterns: Vec<WordData>,
+    n: usize,
+    app: tauri::State<WordleApp>,
+) -> Result<Vec<String>, String> {
+    top_candidate_list(&app.word_bank, &patterns, n).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_pattern_statistics(
+    guess: String,
+    patterns: Vec<WordData>,
+    app: tauri::State<WordleApp>,
+) -> Result<Vec<(String, usize)>, String> {
+    pattern_statistics(&app.word_bank, &guess, &patterns).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_worst_case_remaining(
+    guess: String,
+    patterns: Vec<WordData>,
+    app: tauri::State<WordleApp>,
+) -> Result<usize, String> {
+    worst_case_remaining(&app.word_bank, &guess, &patterns).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_guess_analysis(
+    guess: String,
+    patterns: Vec<WordData>,
+    app: tauri::State<WordleApp>,
+) -> Result<GuessAnalysis, String> {
+    analyze_guess(&app.word_bank, &guess, &patterns).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn check_hard_mode_validity(guess: String, patterns: Vec<WordData>) -> Result<(), String> {
+    hard_mode_validity(&guess, &patterns).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn compute_game_solution(
+    patterns: Vec<WordData>,
+    max_additional_guesses: usize,
+    app: tauri::AppHandle,
+) -> Result<Vec<String>, String> {
+    let word_bank = Arc::clone(&app.state::<WordleApp>().word_bank);
+
+    // The search is quadratic in the candidates, keep it off the async runtime
+    tauri::async_runtime::spawn_blocking(move || {
+        solver::compute_game_solution(&word_bank, &patterns, max_additional_guesses)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn new_game(app: tauri::State<WordleApp>) -> Result<u64, String> {
+    let mut game = GameState::from_word_bank(&app.word_bank, &app.config)
+        .with_solver(Box::new(FrequencySolver));
+    if let Some(secret) = todays_secret(app.word_bank.answers()) {
+        game = game.with_secret(secret);
+    }
+
+    Ok(app.game_states()?.start(game))
+}
+
+#[tauri::command]
+pub fn guess_word(
+    session_id: u64,
+    guess: String,
+    app: tauri::State<WordleApp>,
+) -> Result<GuessResult, String> {
+    let mut sessions = app.game_states()?;
+    let game = sessions.get_mut(session_id)?;
+    let pattern = game.guess(&guess).map_err(|e| e.to_string())?;
+
+    if game.phase() != GamePhase::InProgress {
+        let mut store = app.stats()?;
+        store.stats.update(game.phase(), game.guesses().len());
+        store.save()?;
+    }
+
+    Ok(GuessResult {
+        pattern: word_to_data(&pattern),
+        remaining_candidates: game.current_candidates_count(),
+        guesses_remaining: game.guesses_remaining(),
+        keyboard: keyboard_to_data(&game.keyboard()),
+        phase: game.phase(),
+    })
+}
+
+#[tauri::command]
+pub fn get_hint(session_id: u64, app: tauri::State<WordleApp>) -> Result<String, String> {
+    let mut sessions = app.game_states()?;
+    let game = sessions.get_mut(session_id)?;
+    let hint = game
+        .best_current_guess()
+        .ok_or_else(|| "No candidate is left".to_string())?;
+    game.record_hint();
+
+    Ok(hint)
+}
+
+#[cfg(feature = "rand")]
+#[tauri::command]
+pub fn get_suggestions(
+    session_id: u64,
+    n: usize,
+    app: tauri::State<WordleApp>,
+) -> Result<Vec<String>, String> {
+    let sessions = app.game_states()?;
+    let candidates = sessions.get(session_id)?.candidates();
+
+    let frequencies = letter_presence_counts(candidates);
+    let scores: Vec<(String, f64)> = candidates
+        .iter()
+        .filter_map(|word| {
+            let scored = Word::new(word).ok()?;
+            Some((
+                word.clone(),
+                score_with_presence_counts(&scored, &frequencies),
+            ))
+        })
+        .collect();
+
+    Ok(sample_n_weighted(
+        candidates,
+        &scores,
+        n,
+        &mut rand::thread_rng(),
+    ))
+}
+
+#[cfg(not(feature = "rand"))]
+#[tauri::command]
+pub fn get_suggestions(session_id: u64, n: usize) -> Result<Vec<String>, String> {
+    Err(format!(
+        "The {n} suggestions of game session {session_id} require the `rand` feature"
+    ))
+}
+
+#[tauri::command]
+pub fn export_game_result(
+    session_id: u64,
+    scheme: ColorScheme,
+    app: tauri::State<WordleApp>,
+) -> Result<String, String> {
+    let sessions = app.game_states()?;
+    Ok(sessions.get(session_id)?.share_text(scheme))
+}
+
+#[tauri::command]
+pub fn get_post_game_summary(
+    session_id: u64,
+    scheme: ColorScheme,
+    app: tauri::State<WordleApp>,
+) -> Result<PostGameSummary, String> {
+    let sessions = app.game_states()?;
+    Ok(sessions.get(session_id)?.summarize(scheme))
+}
+
+#[cfg(feature = "image")]
+#[tauri::command]
+pub fn export_board_image(
+    session_id: u64,
+    width: u32,
+    height: u32,
+    app: tauri::State<WordleApp>,
+) -> Result<Vec<u8>, String> {
+    if width == 0 || height == 0 {
+        return Err("The image must not be empty".to_string());
+    }
+    let sessions = app.game_states()?;
+    Ok(sessions.get(session_id)?.to_png(width, height))
+}
+
+#[cfg(not(feature = "image"))]
+#[tauri::command]
+pub fn export_board_image(session_id: u64, width: u32, height: u32) -> Result<Vec<u8>, String> {
+    Err(format!(
+        "The {width}x{height} image of game session {session_id} requires the `image` feature"
+    ))
+}
+
+#[tauri::command]
+pub fn get_game_mode(session_id: u64, app: tauri::State<WordleApp>) -> Result<GameMode, String> {
+    let sessions = app.game_states()?;
+    Ok(sessions.get(session_id)?.game_mode())
+}
 
 #[tauri::command]
-pub fn filter_word_list_command(patterns: Vec<WordData>) -> Result<Vec<String>, String> {
-    filter_word_list(&patterns)
+pub fn set_game_mode(
+    session_id: u64,
+    game_mode: GameMode,
+    app: tauri::State<WordleApp>,
+) -> Result<(), String> {
+    let mut sessions = app.game_states()?;
+    sessions
+        .get_mut(session_id)?
+        .set_game_mode(game_mode)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn evaluate_frequency_solver(app: tauri::AppHandle) -> Result<StrategyStats, String> {
+    let word_bank = Arc::clone(&app.state::<WordleApp>().word_bank);
+
+    // Playing every answer takes seconds, keep it off the async runtime
+    tauri::async_runtime::spawn_blocking(move || {
+        let strategy =
+            |candidates: &[String]| FrequencySolver.next_guess(candidates).unwrap_or_default();
+        evaluate_strategy(strategy, &word_bank, |completed, total| {
+            // Progress is best effort, a window closed meanwhile is not an error
+            let _ = app.emit(
+                EVALUATION_PROGRESS_EVENT,
+                EvaluationProgress { completed, total },
+            );
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_opening_scores(
+    n: usize,
+    app: tauri::AppHandle,
+) -> Result<Vec<(String, f64)>, String> {
+    let state = app.state::<WordleApp>();
+    let word_bank = Arc::clone(&state.word_bank);
+    let path = state.opening_scores_file.clone();
+
+    // Scoring every opener takes seconds, keep it off the async runtime
+    let mut scores = tauri::async_runtime::spawn_blocking(move || {
+        load_opening_scores(&path).unwrap_or_else(|_| {
+            let scores = score_opening_words(&word_bank);
+            // The cache is best effort, the scores are still valid if it cannot be written
+            let _ = save_opening_scores(&scores, &path);
+            scores
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    scores.truncate(n);
+
+    Ok(scores)
+}
+
+#[tauri::command]
+pub fn get_stats(app: tauri::State<WordleApp>) -> Result<WordleStats, String> {
+    Ok(app.stats()?.stats.clone())
+}
+
+#[tauri::command]
+pub fn reset_stats(app: tauri::State<WordleApp>) -> Result<(), String> {
+    let mut store = app.stats()?;
+    store.stats = WordleStats::default();
+    store.save()
+}
+
+#[tauri::command]
+pub fn get_answers(app: tauri::State<WordleApp>) -> Vec<String> {
+    app.word_bank.answers().to_vec()
+}
+
+#[tauri::command]
+pub fn get_allowed_words(app: tauri::State<WordleApp>) -> Vec<String> {
+    app.word_bank.allowed.clone()
+}
+
+#[tauri::command]
+pub fn get_word_data_schema() -> serde_json::Value {
+    word_data_json_schema()
+}
+
+#[cfg(feature = "fuzzy")]
+#[tauri::command]
+pub fn find_similar_words(
+    query: String,
+    max_distance: usize,
+    app: tauri::State<WordleApp>,
+) -> Result<Vec<String>, String> {
+    Ok(fuzzy_lookup(&query, &app.word_bank.allowed, max_distance))
+}
+
+#[cfg(not(feature = "fuzzy"))]
+#[tauri::command]
+pub fn find_similar_words(query: String, max_distance: usize) -> Result<Vec<String>, String> {
+    Err(format!(
+        "Looking up words within {max_distance} edits of '{query}' requires the `fuzzy` feature"
+    ))
+}
+
+#[tauri::command]
+pub fn check_word_validity(word: String, app: tauri::State<WordleApp>) -> WordValidity {
+    WordValidity {
+        is_allowed: app.word_bank.validate_word(&word).is_ok(),
+        is_answer: is_valid_answer(&word, app.word_bank.answers()),
+    }
+}
+
+#[tauri::command]
+pub fn get_letter_frequency(app: tauri::State<WordleApp>) -> serde_json::Value {
+    letter_frequency_table(app.word_bank.answers()).as_json()
+}
+
+#[cfg(feature = "daily")]
+#[tauri::command]
+pub fn get_daily_word(app: tauri::State<WordleApp>) -> Result<String, String> {
+    todays_secret(app.word_bank.answers()).ok_or_else(|| WordleError::EmptyWordList.to_string())
+}
+
+#[cfg(feature = "daily")]
+#[tauri::command]
+pub fn get_daily_word_for_date(
+    date_str: String,
+    app: tauri::State<WordleApp>,
+) -> Result<String, String> {
+    let date =
+        chrono::NaiveDate::parse_from_str(&date_str, "%Y-%m-%d").map_err(|e| e.to_string())?;
+    daily_word(date, app.word_bank.answers())
+        .map(ToString::to_string)
+        .ok_or_else(|| WordleError::EmptyWordList.to_string())
+}
+
+// `generate_handler!` does not accept `#[cfg]` entries, so the commands exist without the feature too
+#[cfg(not(feature = "daily"))]
+#[tauri::command]
+pub fn get_daily_word() -> Result<String, String> {
+    Err("The daily word requires the `daily` feature".to_string())
+}
+
+#[cfg(not(feature = "daily"))]
+#[tauri::command]
+pub fn get_daily_word_for_date(date_str: String) -> Result<String, String> {
+    Err(format!(
+        "The daily word for {date_str} requires the `daily` feature"
+    ))
+}
+
+// Pick the secret word of the day from the calendar date
+#[cfg(feature = "daily")]
+fn todays_secret(words: &[String]) -> Option<String> {
+    todays_word(words).map(ToString::to_string)
+}
+
+// Pick the secret word of the day, changing once every 24 hours
+#[cfg(not(feature = "daily"))]
+fn todays_secret(words: &[String]) -> Option<String> {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() / 86_400);
+    let index = usize::try_from(days).ok()? % words.len().max(1);
+
+    words.get(index).cloned()
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![filter_word_list_command])
+        .setup(|app| {
+            let data_dir = app.path().app_data_dir()?;
+            #[cfg(feature = "config")]
+            let config = WordleConfig::from_toml(&data_dir.join(CONFIG_FILE))?;
+            #[cfg(not(feature = "config"))]
+            let config = WordleConfig::from_env();
+            app.manage(WordleApp::new(config, &data_dir)?);
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            filter_word_list_command,
+            filter_word_list_with_options_command,
+            debug_filter_word_list_command,
+            get_top_candidates,
+            get_pattern_statistics,
+            get_worst_case_remaining,
+            get_guess_analysis,
+            check_hard_mode_validity,
+            compute_game_solution,
+            new_game,
+            guess_word,
+            get_hint,
+            get_suggestions,
+            export_game_result,
+            export_board_image,
+            get_post_game_summary,
+            get_game_mode,
+            set_game_mode,
+            evaluate_frequency_solver,
+            get_opening_scores,
+            get_stats,
+            reset_stats,
+            get_answers,
+            get_allowed_words,
+            check_word_validity,
+            get_letter_frequency,
+            find_similar_words,
+            get_word_data_schema,
+            get_daily_word,
+            get_daily_word_for_date
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -19,29 +608,65 @@ mod tests {
     use serde_json::{json, Value};
 
     use super::*;
+    use crate::game_logic::filter_word_list;
 
     // Helper function to parse JSON into WordData
     fn parse_word_data(json_data: Value) -> Vec<WordData> {
         serde_json::from_value(json_data).expect("Failed to parse JSON data")
     }
 
+    #[test]
+    fn test_game_sessions() {
+        let mut sessions = GameSessions::default();
+        let first = sessions.start(GameState::new(vec!["crane".to_string()]));
+        let second = sessions.start(GameState::new(vec!["slate".to_string()]));
+        assert_ne!(first, second);
+
+        assert_eq!(
+            sessions.get(second).unwrap().word_bank(),
+            &["slate".to_string()]
+        );
+        assert!(sessions.get_mut(second + 1).is_err());
+    }
+
+    #[test]
+    fn test_wordle_app_new() {
+        let data_dir = std::env::temp_dir();
+        let app = WordleApp::new(WordleConfig::default(), &data_dir).unwrap();
+        assert!(!app.word_bank.answers().is_empty());
+        assert!(app.game_states().unwrap().get(0).is_err());
+        assert_eq!(app.opening_scores_file, data_dir.join(OPENING_SCORES_FILE));
+
+        let config = WordleConfig {
+            words_file: PathBuf::from("assets/missing.json"),
+            ..WordleConfig::default()
+        };
+        assert!(WordleApp::new(config, &data_dir).is_err());
+    }
+
+    #[test]
+    fn test_todays_secret() {
+        let words = vec!["crane".to_string(), "slate".to_string()];
+        let secret = todays_secret(&words).unwrap();
+        assert!(words.contains(&secret));
+        assert!(todays_secret(&[]).is_none());
+    }
+
     #[test]
     fn test_filter_word_list_basic() {
         // Single pattern with one correct letter
         let json_patterns = json!([
-            {
-                "letters": [
-                    {"character": "p", "state": "correct"},
-                    {"character": "a", "state": "unknown"},
-                    {"character": "i", "state": "unknown"},
-                    {"character": "n", "state": "unknown"},
-                    {"character": "t", "state": "unknown"}
-                ]
-            }
+            [
+                {"character": "p", "state": "correct"},
+                {"character": "a", "state": "unknown"},
+                {"character": "i", "state": "unknown"},
+                {"character": "n", "state": "unknown"},
+                {"character": "t", "state": "unknown"}
+            ]
         ]);
 
         let patterns = parse_word_data(json_patterns);
-        let result = filter_word_list(&patterns).unwrap();
+        let result = filter_word_list(&WordBank::load(), &patterns).unwrap();
 
         // Results should contain words starting with 'p'
         assert!(result.iter().all(|w| w.starts_with('p')));
@@ -49,32 +674,53 @@ mod tests {
         assert!(result.contains(&"place".to_string()));
     }
 
+    #[test]
+    fn test_compute_game_solution() {
+        let json_patterns = json!([
+            [
+                {"character": "t", "state": "absent"},
+                {"character": "r", "state": "correct"},
+                {"character": "a", "state": "correct"},
+                {"character": "c", "state": "correct"},
+                {"character": "e", "state": "correct"}
+            ]
+        ]);
+        let patterns = parse_word_data(json_patterns);
+        let word_bank = WordBank::load();
+        let candidates = filter_word_list(&word_bank, &patterns).unwrap();
+        assert!(candidates.len() > 1);
+
+        // Every candidate differs by its first letter only, so the games cannot share
+        // more than the next guess
+        let solution = solver::compute_game_solution(&word_bank, &patterns, 6).unwrap();
+        assert_eq!(solution.len(), 1);
+        assert!(candidates.contains(&solution[0]));
+
+        assert!(solver::compute_game_solution(&word_bank, &patterns, 1).is_err());
+    }
+
     #[test]
     fn test_filter_word_list_multiple_patterns() {
         // Test with multiple patterns
         let json_patterns = json!([
-            {
-                "letters": [
-                    {"character": "t", "state": "unknown"},
-                    {"character": "r", "state": "unknown"},
-                    {"character": "a", "state": "correct"},
-                    {"character": "i", "state": "unknown"},
-                    {"character": "n", "state": "unknown"}
-                ]
-            },
-            {
-                "letters": [
-                    {"character": "p", "state": "unknown"},
-                    {"character": "l", "state": "unknown"},
-                    {"character": "a", "state": "unknown"},
-                    {"character": "n", "state": "correct"},
-                    {"character": "e", "state": "unknown"}
-                ]
-            }
+            [
+                {"character": "t", "state": "unknown"},
+                {"character": "r", "state": "unknown"},
+                {"character": "a", "state": "correct"},
+                {"character": "i", "state": "unknown"},
+                {"character": "n", "state": "unknown"}
+            ],
+            [
+                {"character": "p", "state": "unknown"},
+                {"character": "l", "state": "unknown"},
+                {"character": "a", "state": "unknown"},
+                {"character": "n", "state": "correct"},
+                {"character": "e", "state": "unknown"}
+            ]
         ]);
 
         let patterns = parse_word_data(json_patterns);
-        let result = filter_word_list(&patterns).unwrap();
+        let result = filter_word_list(&WordBank::load(), &patterns).unwrap();
 
         // Results should have 'a' at position 2 and 'n' at position 3
         assert!(result.iter().all(|w| {
@@ -87,19 +733,17 @@ mod tests {
     fn test_filter_word_list_misplaced() {
         // Test with misplaced letters
         let json_patterns = json!([
-            {
-                "letters": [
-                    {"character": "r", "state": "misplaced"},
-                    {"character": "e", "state": "unknown"},
-                    {"character": "a", "state": "unknown"},
-                    {"character": "c", "state": "unknown"},
-                    {"character": "h", "state": "unknown"}
-                ]
-            }
+            [
+                {"character": "r", "state": "misplaced"},
+                {"character": "e", "state": "unknown"},
+                {"character": "a", "state": "unknown"},
+                {"character": "c", "state": "unknown"},
+                {"character": "h", "state": "unknown"}
+            ]
         ]);
 
         let patterns = parse_word_data(json_patterns);
-        let result = filter_word_list(&patterns).unwrap();
+        let result = filter_word_list(&WordBank::load(), &patterns).unwrap();
 
         // Results should contain 'r' but not at first position
         assert!(result.iter().all(|w| {
@@ -108,23 +752,53 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn test_word_data_schema() {
+        let schema = word_data_json_schema();
+        assert_eq!(schema["minItems"], 5);
+        assert_eq!(schema["maxItems"], 5);
+        assert_eq!(
+            schema["items"]["properties"]["state"]["enum"],
+            json!(["unknown", "correct", "misplaced", "absent"])
+        );
+    }
+
+    #[test]
+    fn test_filter_word_list_non_ascii() {
+        let json_patterns = json!([
+            [
+                {"character": "c", "state": "unknown"},
+                {"character": "r", "state": "unknown"},
+                {"character": "é", "state": "unknown"},
+                {"character": "n", "state": "unknown"},
+                {"character": "e", "state": "unknown"}
+            ]
+        ]);
+
+        let patterns = parse_word_data(json_patterns);
+        assert_eq!(
+            filter_word_list(&WordBank::load(), &patterns),
+            Err(WordleError::ConversionError(
+                "character 'é' at position 2 is not a valid ASCII letter".to_string()
+            ))
+        );
+    }
+
     #[test]
     fn test_filter_word_list_absent() {
         // Test with absent letters
         let json_patterns = json!([
-            {
-                "letters": [
-                    {"character": "q", "state": "absent"},
-                    {"character": "w", "state": "absent"},
-                    {"character": "e", "state": "absent"},
-                    {"character": "r", "state": "absent"},
-                    {"character": "t", "state": "absent"}
-                ]
-            }
+            [
+                {"character": "q", "state": "absent"},
+                {"character": "w", "state": "absent"},
+                {"character": "e", "state": "absent"},
+                {"character": "r", "state": "absent"},
+                {"character": "t", "state": "absent"}
+            ]
         ]);
 
         let patterns = parse_word_data(json_patterns);
-        let result = filter_word_list(&patterns).unwrap();
+        let result = filter_word_list(&WordBank::load(), &patterns).unwrap();
 
         // Results should not contain any of the letters q, w, e, r, t
         assert!(result.iter().all(|w| {
@@ -140,37 +814,37 @@ mod tests {
     fn test_filter_word_list_invalid_input() {
         // Test with invalid input (too few letters)
         let json_patterns = json!([
-            {
-                "letters": [
-                    {"character": "h", "state": "unknown"},
-                    {"character": "i", "state": "unknown"}
-                ]
-            }
+            [
+                {"character": "h", "state": "unknown"},
+                {"character": "i", "state": "unknown"}
+            ]
         ]);
 
         let patterns = parse_word_data(json_patterns);
-        let result = filter_word_list(&patterns);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("must have exactly 5 letters"));
+        assert_eq!(
+            filter_word_list(&WordBank::load(), &patterns),
+            Err(WordleError::InvalidLength {
+                expected: 5,
+                got: 2
+            })
+        );
     }
 
     #[test]
     fn test_filter_word_list_mixed_constraints() {
         // Test with a mix of constraints
         let json_patterns = json!([
-            {
-                "letters": [
-                    {"character": "b", "state": "unknown"},
-                    {"character": "l", "state": "correct"},
-                    {"character": "a", "state": "correct"},
-                    {"character": "c", "state": "unknown"},
-                    {"character": "k", "state": "absent"}
-                ]
-            }
+            [
+                {"character": "b", "state": "unknown"},
+                {"character": "l", "state": "correct"},
+                {"character": "a", "state": "correct"},
+                {"character": "c", "state": "unknown"},
+                {"character": "k", "state": "absent"}
+            ]
         ]);
 
         let patterns = parse_word_data(json_patterns);
-        let result = filter_word_list(&patterns).unwrap();
+        let result = filter_word_list(&WordBank::load(), &patterns).unwrap();
 
         // Results should have 'l' at position 1, 'a' at position 2, and no 'k' at the end
         assert!(result.iter().all(|w| {
@@ -183,19 +857,17 @@ mod tests {
     fn test_direct_json_string() {
         // Test with direct JSON string parsing
         let json_str = r#"[
-                {
-                    "letters": [
-                        {"character": "s", "state": "correct"},
-                        {"character": "t", "state": "correct"},
-                        {"character": "o", "state": "unknown"},
-                        {"character": "n", "state": "unknown"},
-                        {"character": "e", "state": "unknown"}
-                    ]
-                }
+                [
+                    {"character": "s", "state": "correct"},
+                    {"character": "t", "state": "correct"},
+                    {"character": "o", "state": "unknown"},
+                    {"character": "n", "state": "unknown"},
+                    {"character": "e", "state": "unknown"}
+                ]
             ]"#;
 
         let patterns: Vec<WordData> = serde_json::from_str(json_str).unwrap();
-        let result = filter_word_list(&patterns).unwrap();
+        let result = filter_word_list(&WordBank::load(), &patterns).unwrap();
 
         // Results should start with "st"
         assert!(result.iter().all(|w| w.starts_with("st")));
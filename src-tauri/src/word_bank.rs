@@ -0,0 +1,742 @@
+//! Module containing the bank of valid words.
+
+#[cfg(feature = "save")]
+use std::io::{BufWriter, Write};
+use std::{collections::HashMap, fs::File, io::BufReader, path::Path};
+
+use crate::{
+    constraints::LetterConstraints,
+    game_logic::{dedup_candidates, filter_words_by_constraints, filter_words_deterministic},
+    load_words, Word, WordleError,
+};
+
+/// # `WordBank`
+/// The words that can be the secret and the words accepted as guesses, without duplicates.
+/// Every answer is also an allowed guess.
+///
+/// The answers are indexed when the bank is built, they can only be added with
+/// `add_word` so the lookups never miss them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WordBank {
+    answers: Vec<String>,
+    pub allowed: Vec<String>,
+    // The indices in `answers` of the answers with each letter (`a` to `z`) at each position
+    by_letter_at: [[Vec<u32>; 5]; 26],
+    // The indices in `answers` of the answers containing each letter
+    containing: [Vec<u32>; 26],
+    // The indices in `answers` of the answers by their sorted letters
+    anagrams: HashMap<[char; 5], Vec<u32>>,
+}
+
+impl WordBank {
+    // Build the bank and the indices of its answers
+    fn indexed(answers: Vec<String>, allowed: Vec<String>) -> Self {
+        let mut bank = Self {
+            answers: Vec::new(),
+            allowed,
+            by_letter_at: Default::default(),
+            containing: Default::default(),
+            anagrams: HashMap::new(),
+        };
+        for answer in answers {
+            bank.index_answer(answer);
+        }
+
+        bank
+    }
+
+    fn index_answer(&mut self, answer: String) {
+        if answer.len() == 5 && answer.bytes().all(|b| b.is_ascii_alphabetic()) {
+            let index = u32::try_from(self.answers.len()).expect("fewer than 2^32 answers");
+            for (pos, b) in answer.bytes().enumerate() {
+                let letter = (b.to_ascii_lowercase() - b'a') as usize;
+                self.by_letter_at[letter][pos].push(index);
+            }
+            let mut seen = [false; 26];
+            for b in answer.bytes() {
+                let letter = (b.to_ascii_lowercase() - b'a') as usize;
+                if !std::mem::replace(&mut seen[letter], true) {
+                    self.containing[letter].push(index);
+                }
+            }
+            // SAFETY: the answer was just checked to be exactly 5 ASCII letters
+            let key = unsafe { Word::new_unchecked(&answer) }.to_sorted_chars();
+            self.anagrams.entry(key).or_default().push(index);
+        }
+        self.answers.push(answer);
+    }
+
+    // The answers at the given indices of an index
+    fn answers_at<'a>(&'a self, indices: &'a [u32]) -> impl ExactSizeIterator<Item = &'a str> {
+        indices
+            .iter()
+            .map(|&index| self.answers[index as usize].as_str())
+    }
+
+    fn letter_at_indices(&self, letter: char, pos: usize) -> &[u32] {
+        if !letter.is_ascii_alphabetic() || pos >= 5 {
+            return &[];
+        }
+
+        &self.by_letter_at[(letter.to_ascii_lowercase() as u8 - b'a') as usize][pos]
+    }
+
+    fn containing_indices(&self, letter: char) -> &[u32] {
+        if !letter.is_ascii_alphabetic() {
+            return &[];
+        }
+
+        &self.containing[(letter.to_ascii_lowercase() as u8 - b'a') as usize]
+    }
+
+    /// # `from_words`
+    /// Creates a word bank where every word is both an answer and an allowed guess.
+    ///
+    /// ## Arguments
+    /// * `words` - The list of words.
+    #[must_use]
+    pub fn from_words(words: Vec<String>) -> Self {
+        let words = dedup_candidates(words);
+        Self::indexed(words.clone(), words)
+    }
+
+    /// # `from_lists`
+    /// Creates a word bank from separate answers and guesses lists, dropping duplicates.
+    /// Answers missing from the guesses list are allowed as well.
+    ///
+    /// ## Arguments
+    /// * `answers` - The words that can be the secret.
+    /// * `guesses` - The words accepted as guesses.
+    #[must_use]
+    pub fn from_lists(answers: Vec<String>, guesses: Vec<String>) -> Self {
+        let answers = dedup_candidates(answers);
+        let allowed = dedup_candidates(guesses.into_iter().chain(answers.clone()).collect());
+
+        Self::indexed(answers, allowed)
+    }
+
+    /// # `load`
+    /// Creates a word bank from the bundled word list.
+    #[must_use]
+    pub fn load() -> Self {
+        Self::from_words(load_words())
+    }
+
+    /// # `load_file`
+    /// Creates a word bank from a JSON file holding an array of words.
+    ///
+    /// ## Arguments
+    /// * `path` - The file of the words, used both as answers and guesses.
+    ///
+    /// ## Returns
+    /// * `Result<WordBank, WordleError>` - The word bank or an error if the file is invalid.
+    pub fn load_file(path: &Path) -> Result<Self, WordleError> {
+        Ok(Self::from_words(read_word_list(path)?))
+    }
+
+    /// # `load_dual`
+    /// Creates a word bank from two JSON files, each holding an array of words.
+    ///
+    /// ## Arguments
+    /// * `answers_path` - The file of the words that can be the secret.
+    /// * `guesses_path` - The file of the words accepted as guesses.
+    ///
+    /// ## Returns
+    /// * `Result<WordBank, WordleError>` - The word bank or an error if a file is invalid.
+    pub fn load_dual(answers_path: &Path, guesses_path: &Path) -> Result<Self, WordleError> {
+        Ok(Self::from_lists(
+            read_word_list(answers_path)?,
+            read_word_list(guesses_path)?,
+        ))
+    }
+
+    /// # `answers`
+    /// Returns the words that can be the secret, in the order they were added.
+    #[must_use]
+    pub fn answers(&self) -> &[String] {
+        &self.answers
+    }
+
+    /// # `words_with_letter_at`
+    /// Returns the answers with a letter at a position, without scanning the answers.
+    ///
+    /// ## Arguments
+    /// * `letter` - The letter to look for, case-insensitive.
+    /// * `pos` - The position of the letter, from 0 to 4.
+    ///
+    /// ## Returns
+    /// * `impl ExactSizeIterator<Item = &str>` - The matching answers in the order of
+    ///   `answers`, none if the letter or the position is invalid.
+    pub fn words_with_letter_at(
+        &self,
+        letter: char,
+        pos: usize,
+    ) -> impl ExactSizeIterator<Item = &str> {
+        self.answers_at(self.letter_at_indices(letter, pos))
+    }
+
+    /// # `words_containing`
+    /// Returns the answers containing a letter, without scanning the answers.
+    ///
+    /// ## Arguments
+    /// * `letter` - The letter to look for, case-insensitive.
+    ///
+    /// ## Returns
+    /// * `impl ExactSizeIterator<Item = &str>` - The matching answers in the order of
+    ///   `answers`, each once, none if the letter is invalid.
+    pub fn words_containing(&self, letter: char) -> impl ExactSizeIterator<Item = &str> {
+        self.answers_at(self.containing_indices(letter))
+    }
+
+    /// # `anagrams_of`
+    /// Returns the answers made of the same letters as a word, without scanning the answers.
+    ///
+    /// ## Arguments
+    /// * `word` - The word to rearrange, its states are ignored.
+    ///
+    /// ## Returns
+    /// * `Vec<String>` - The matching answers in the order of `answers`, including the
+    ///   word itself if it is an answer.
+    #[must_use]
+    pub fn anagrams_of(&self, word: &Word) -> Vec<String> {
+        self.anagrams
+            .get(&word.to_sorted_chars())
+            .map(|indices| self.answers_at(indices).map(ToString::to_string).collect())
+            .unwrap_or_default()
+    }
+
+    /// # `contains`
+    /// Checks if a word is accepted as a guess.
+    #[must_use]
+    pub fn contains(&self, word: &str) -> bool {
+        self.allowed.iter().any(|w| w == word)
+    }
+
+    /// # `validate_word`
+    /// Checks that a word is a valid guess.
+    ///
+    /// ## Arguments
+    /// * `word` - The word to check, case-insensitive.
+    ///
+    /// ## Returns
+    /// * `Result<(), WordleError>` - An error if the word is invalid or not allowed.
+    pub fn validate_word(&self, word: &str) -> Result<(), WordleError> {
+        Word::new(word)?;
+
+        let word = word.to_ascii_lowercase();
+        if !self.contains(&word) {
+            return Err(WordleError::NotInWordBank(word));
+        }
+
+        Ok(())
+    }
+
+    /// # `filter`
+    /// Returns the answers matching every pattern, in alphabetical order.
+    ///
+    /// ## Arguments
+    /// * `patterns` - The guesses with their states.
+    #[must_use]
+    pub fn filter(&self, patterns: &[Word]) -> Vec<String> {
+        filter_words_deterministic(&self.answers, patterns)
+    }
+
+    /// # `filter_to_subbank`
+    /// Returns a bank of the answers matching every pattern, for chaining filters.
+    /// The allowed guesses are kept.
+    ///
+    /// ## Arguments
+    /// * `patterns` - The guesses with their states.
+    #[must_use]
+    pub fn filter_to_subbank(&self, patterns: &[Word]) -> WordBank {
+        self.filter_to_subbank_with_constraints(&LetterConstraints::from_patterns(patterns))
+    }
+
+    /// # `filter_to_subbank_with_constraints`
+    /// Same as `filter_to_subbank`, with already compiled constraints.
+    ///
+    /// ## Arguments
+    /// * `constraints` - The constraints every answer must satisfy.
+    #[must_use]
+    pub fn filter_to_subbank_with_constraints(&self, constraints: &LetterConstraints) -> WordBank {
+        // Every answer has the known and required letters, start from the fewest words
+        // having one of them
+        let known = (0..5).filter_map(|pos| {
+            constraints.known[pos].map(|letter| self.letter_at_indices(letter, pos))
+        });
+        let required = constraints
+            .required
+            .iter()
+            .map(|&letter| self.containing_indices(letter));
+        let answers = match known.chain(required).min_by_key(|indices| indices.len()) {
+            // Indexed answers are valid words, only the constraints are left to check
+            Some(indices) => self
+                .answers_at(indices)
+                .filter(|answer| Word::new(answer).is_ok_and(|w| constraints.is_satisfied_by(&w)))
+                .map(ToString::to_string)
+                .collect(),
+            None => filter_words_by_constraints(&self.answers, constraints),
+        };
+
+        WordBank::indexed(answers, self.allowed.clone())
+    }
+
+    /// # `random_secret`
+    /// Picks a random answer.
+    ///
+    /// ## Arguments
+    /// * `rng` - The random number generator.
+    ///
+    /// ## Returns
+    /// * `Option<&str>` - The answer, or `None` if the bank has no answers.
+    #[cfg(feature = "rand")]
+    pub fn random_secret(&self, rng: &mut impl rand::Rng) -> Option<&str> {
+        use rand::seq::SliceRandom;
+
+        self.answers.choose(rng).map(String::as_str)
+    }
+
+    /// # `len`
+    /// Returns the number of answers in the bank.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    /// # `is_empty`
+    /// Checks if the bank has no answers.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+
+    /// # `add_word`
+    /// Adds a custom word to the bank, both as an answer and as an allowed guess.
+    ///
+    /// ## Arguments
+    /// * `word` - The word to add, it is stored lowercased.
+    ///
+    /// ## Returns
+    /// * `Result<(), WordleError>` - An error if the word is invalid or already in the bank.
+    pub fn add_word(&mut self, word: &str) -> Result<(), WordleError> {
+        Word::new(word)?;
+
+        let word = word.to_ascii_lowercase();
+        if self.answers.contains(&word) {
+            return Err(WordleError::DuplicateWord(word));
+        }
+        if !self.contains(&word) {
+            self.allowed.push(word.clone());
+        }
+        self.index_answer(word);
+
+        Ok(())
+    }
+}
+
+impl Word {
+    /// # `is_in_word_bank`
+    /// Checks if the word is accepted as a guess by a word bank.
+    ///
+    /// ## Arguments
+    /// * `bank` - The word bank to look in.
+    #[must_use]
+    pub fn is_in_word_bank(&self, bank: &WordBank) -> bool {
+        bank.contains(&self.to_string())
+    }
+
+    /// # `try_from_bank`
+    /// Creates a word, checking that it is accepted as a guess by a word bank.
+    ///
+    /// ## Arguments
+    /// * `s` - The word, case-insensitive.
+    /// * `bank` - The word bank to look in.
+    ///
+    /// ## Returns
+    /// * `Result<Word, WordleError>` - The word, an error if it is not 5 ASCII letters or
+    ///   `NotInWordBank` if the bank does not accept it.
+    pub fn try_from_bank(s: &str, bank: &WordBank) -> Result<Self, WordleError> {
+        let word = Self::new(s)?;
+        if !word.is_in_word_bank(bank) {
+            return Err(WordleError::NotInWordBank(s.to_ascii_lowercase()));
+        }
+
+        Ok(word)
+    }
+}
+
+/// # `is_valid_answer`
+/// Checks if a word is in the answers list, i.e. could be the secret.
+///
+/// ## Arguments
+/// * `word` - The word to check, case-insensitive.
+/// * `answers` - The answers list.
+#[must_use]
+pub fn is_valid_answer(word: &str, answers: &[String]) -> bool {
+    let word = word.to_ascii_lowercase();
+    answers.contains(&word)
+}
+
+/// # `is_common_word`
+/// Checks if a word is frequent enough to be a likely answer, for when there is no
+/// separate answers list.
+///
+/// ## Arguments
+/// * `word` - The word to check, case-insensitive.
+/// * `freq_table` - The number of occurrences of each word in some corpus.
+/// * `threshold` - The minimum number of occurrences.
+#[must_use]
+pub fn is_common_word(word: &str, freq_table: &HashMap<String, u32>, threshold: u32) -> bool {
+    freq_table
+        .get(&word.to_ascii_lowercase())
+        .is_some_and(|&count| count >= threshold)
+}
+
+/// How peaked `sample_n_weighted` is around the best scores once they are rescaled to [0, 1].
+#[cfg(feature = "rand")]
+const SAMPLING_TEMPERATURE: f64 = 0.1;
+
+/// # `sample_n_weighted`
+/// Picks `n` distinct words at random, favoring the best scoring ones (softmax over the
+/// scores), so that suggestions vary while staying good.
+///
+/// ## Arguments
+/// * `words` - The words to pick from.
+/// * `scores` - The score of each word, higher is better, missing words are never picked.
+/// * `n` - The number of words to pick.
+/// * `rng` - The random number generator.
+///
+/// ## Returns
+/// * `Vec<String>` - At most `n` words, in the order they were picked.
+#[cfg(feature = "rand")]
+pub fn sample_n_weighted(
+    words: &[String],
+    scores: &[(String, f64)],
+    n: usize,
+    rng: &mut impl rand::Rng,
+) -> Vec<String> {
+    use rand::seq::SliceRandom;
+
+    let scores: HashMap<&str, f64> = scores.iter().map(|(w, s)| (w.as_str(), *s)).collect();
+    let scored: Vec<(&String, f64)> = words
+        .iter()
+        .filter_map(|word| scores.get(word.as_str()).map(|score| (word, *score)))
+        .collect();
+
+    // Rescale so that the unit of the scores does not matter
+    let max = scored
+        .iter()
+        .map(|(_, s)| *s)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let min = scored.iter().map(|(_, s)| *s).fold(f64::INFINITY, f64::min);
+    let range = if max > min { max - min } else { 1.0 };
+
+    scored
+        .choose_multiple_weighted(rng, n, |(_, score)| {
+            ((score - max) / range / SAMPLING_TEMPERATURE).exp()
+        })
+        .map(|chosen| chosen.map(|(word, _)| (*word).clone()).collect())
+        .unwrap_or_default()
+}
+
+/// # `save_words`
+/// Saves a word list as a compact JSON array, the smallest file to ship.
+///
+/// ## Arguments
+/// * `words` - The words to save.
+/// * `path` - The path of the JSON file.
+///
+/// ## Returns
+/// * `Result<(), WordleError>` - An error if the file could not be written.
+#[cfg(feature = "save")]
+pub fn save_words(words: &[String], path: &Path) -> Result<(), WordleError> {
+    write_word_list(words, path, false)
+}
+
+/// # `save_words_pretty`
+/// Saves a word list as a JSON array with one word per line, so that changes to
+/// custom word lists give readable diffs.
+///
+/// ## Arguments
+/// * `words` - The words to save.
+/// * `path` - The path of the JSON file.
+///
+/// ## Returns
+/// * `Result<(), WordleError>` - An error if the file could not be written.
+#[cfg(feature = "save")]
+pub fn save_words_pretty(words: &[String], path: &Path) -> Result<(), WordleError> {
+    write_word_list(words, path, true)
+}
+
+#[cfg(feature = "save")]
+fn write_word_list(words: &[String], path: &Path, pretty: bool) -> Result<(), WordleError> {
+    let file = File::create(path).map_err(|e| WordleError::Io(e.to_string()))?;
+    let mut writer = BufWriter::new(file);
+    if pretty {
+        serde_json::to_writer_pretty(&mut writer, words)
+    } else {
+        serde_json::to_writer(&mut writer, words)
+    }
+    .map_err(|e| WordleError::Io(e.to_string()))?;
+    writer.flush().map_err(|e| WordleError::Io(e.to_string()))
+}
+
+fn read_word_list(path: &Path) -> Result<Vec<String>, WordleError> {
+    let file = File::open(path).map_err(|e| WordleError::Io(e.to_string()))?;
+    serde_json::from_reader(BufReader::new(file)).map_err(|e| WordleError::Io(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_words_dedups() {
+        let bank = WordBank::from_words(vec![
+            "crane".to_string(),
+            "slate".to_string(),
+            "crane".to_string(),
+        ]);
+        assert_eq!(bank.answers(), &["crane".to_string(), "slate".to_string()]);
+        assert!(bank.contains("slate"));
+        assert!(!bank.contains("pious"));
+    }
+
+    #[test]
+    fn test_add_word() {
+        let mut bank = WordBank::from_words(vec!["crane".to_string()]);
+        bank.add_word("Slate").unwrap();
+        assert_eq!(bank.len(), 2);
+        assert!(bank.contains("slate"));
+
+        assert_eq!(
+            bank.add_word("CRANE"),
+            Err(WordleError::DuplicateWord("crane".to_string()))
+        );
+        assert_eq!(
+            bank.add_word("cranes"),
+            Err(WordleError::InvalidLength {
+                expected: 5,
+                got: 6
+            })
+        );
+        assert_eq!(bank.len(), 2);
+    }
+
+    #[test]
+    fn test_filter_to_subbank() {
+        let bank = WordBank::from_lists(
+            vec![
+                "crane".to_string(),
+                "trace".to_string(),
+                "pious".to_string(),
+            ],
+            vec!["xylyl".to_string()],
+        );
+        let first = Word::from_pattern_code("pious", "AAAAA").unwrap();
+        let second = Word::from_pattern_code("crane", "YGGAG").unwrap();
+
+        let subbank = bank.filter_to_subbank(std::slice::from_ref(&first));
+        assert_eq!(
+            subbank.answers(),
+            &["crane".to_string(), "trace".to_string()]
+        );
+        assert!(subbank.contains("xylyl"));
+
+        let chained = subbank.filter_to_subbank(std::slice::from_ref(&second));
+        assert_eq!(chained.answers(), &["trace".to_string()]);
+        assert_eq!(chained.answers(), bank.filter(&[first, second]));
+    }
+
+    #[test]
+    fn test_words_with_letter_at() {
+        let mut bank = WordBank::from_words(vec![
+            "crane".to_string(),
+            "trace".to_string(),
+            "pious".to_string(),
+        ]);
+        assert!(bank.words_with_letter_at('R', 1).eq(["crane", "trace"]));
+        assert!(bank.words_with_letter_at('p', 0).eq(["pious"]));
+        assert_eq!(bank.words_with_letter_at('z', 0).len(), 0);
+        assert_eq!(bank.words_with_letter_at('r', 5).len(), 0);
+        assert_eq!(bank.words_with_letter_at('!', 0).len(), 0);
+
+        bank.add_word("brace").unwrap();
+        assert_eq!(bank.words_with_letter_at('r', 1).len(), 3);
+    }
+
+    #[test]
+    fn test_words_containing() {
+        let mut bank = WordBank::from_words(vec![
+            "geese".to_string(),
+            "trace".to_string(),
+            "pious".to_string(),
+        ]);
+        assert!(bank.words_containing('E').eq(["geese", "trace"]));
+        assert_eq!(bank.words_containing('z').len(), 0);
+        assert_eq!(bank.words_containing('!').len(), 0);
+
+        bank.add_word("zebra").unwrap();
+        assert!(bank.words_containing('z').eq(["zebra"]));
+
+        // Misplaced letters narrow the answers down through the index
+        let pattern = Word::from_pattern_code("mossy", "UUUYU").unwrap();
+        assert_eq!(
+            bank.filter_to_subbank(&[pattern]).answers(),
+            &["pious".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_anagrams_of() {
+        let mut bank = WordBank::from_words(
+            ["crate", "trace", "slate", "react", "caret", "toolong"]
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        );
+        assert_eq!(
+            bank.anagrams_of(&Word::new("CATER").unwrap()),
+            vec!["crate", "trace", "react", "caret"]
+        );
+        assert_eq!(
+            bank.anagrams_of(&Word::new("least").unwrap()),
+            vec!["slate"]
+        );
+        assert!(bank.anagrams_of(&Word::new("pious").unwrap()).is_empty());
+
+        bank.add_word("Stale").unwrap();
+        assert_eq!(
+            bank.anagrams_of(&Word::new("least").unwrap()),
+            vec!["slate", "stale"]
+        );
+    }
+
+    #[test]
+    fn test_dual_lists() {
+        let bank = WordBank::from_lists(
+            vec!["crane".to_string(), "slate".to_string()],
+            vec!["crane".to_string(), "xylyl".to_string()],
+        );
+        assert_eq!(bank.len(), 2);
+        assert_eq!(bank.allowed.len(), 3);
+
+        assert!(bank.validate_word("XYLYL").is_ok());
+        assert!(bank.validate_word("slate").is_ok());
+        assert_eq!(
+            bank.validate_word("pious"),
+            Err(WordleError::NotInWordBank("pious".to_string()))
+        );
+
+        // Only the answers are filtered
+        let pattern = Word::from_pattern_code("xylyl", "AAAAA").unwrap();
+        assert_eq!(bank.filter(&[pattern]), vec!["crane".to_string()]);
+    }
+
+    #[test]
+    fn test_word_in_bank() {
+        let bank = WordBank::from_lists(vec!["crane".to_string()], vec!["xylyl".to_string()]);
+
+        assert!(Word::new("XYLYL").unwrap().is_in_word_bank(&bank));
+        assert!(!Word::new("pious").unwrap().is_in_word_bank(&bank));
+
+        assert_eq!(
+            Word::try_from_bank("Crane", &bank),
+            Ok(Word::new("crane").unwrap())
+        );
+        assert_eq!(
+            Word::try_from_bank("Pious", &bank),
+            Err(WordleError::NotInWordBank("pious".to_string()))
+        );
+        assert_eq!(
+            Word::try_from_bank("cr4ne", &bank),
+            Err(WordleError::NonAlphabeticCharacter('4'))
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample_n_weighted() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let words: Vec<String> = ["crane", "slate", "pious", "xylyl"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let scores = vec![
+            ("crane".to_string(), 10.0),
+            ("slate".to_string(), 9.0),
+            ("pious".to_string(), 1.0),
+        ];
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let sample = sample_n_weighted(&words, &scores, 2, &mut rng);
+        assert_eq!(sample.len(), 2);
+        assert_ne!(sample[0], sample[1]);
+        // Unscored words are never picked
+        let sample = sample_n_weighted(&words, &scores, 10, &mut rng);
+        assert_eq!(sample.len(), 3);
+        assert!(!sample.contains(&"xylyl".to_string()));
+
+        // The best words are picked far more often
+        let crane = (0..100)
+            .filter(|_| sample_n_weighted(&words, &scores, 1, &mut rng)[0] == "crane")
+            .count();
+        assert!(crane > 50);
+    }
+
+    #[test]
+    fn test_answer_checks() {
+        let answers = vec!["crane".to_string()];
+        assert!(is_valid_answer("CRANE", &answers));
+        assert!(!is_valid_answer("xylyl", &answers));
+
+        let freq_table = HashMap::from([("crane".to_string(), 120), ("xylyl".to_string(), 2)]);
+        assert!(is_common_word("crane", &freq_table, 100));
+        assert!(!is_common_word("xylyl", &freq_table, 100));
+        assert!(!is_common_word("pious", &freq_table, 100));
+    }
+
+    #[test]
+    fn test_load_dual() {
+        let dir = std::env::temp_dir();
+        let answers_path = dir.join(format!("wordle_answers_{}.json", std::process::id()));
+        let guesses_path = dir.join(format!("wordle_guesses_{}.json", std::process::id()));
+        std::fs::write(&answers_path, r#"["crane"]"#).unwrap();
+        std::fs::write(&guesses_path, r#"["slate"]"#).unwrap();
+
+        let bank = WordBank::load_dual(&answers_path, &guesses_path).unwrap();
+        assert_eq!(bank.answers(), vec!["crane".to_string()]);
+        assert_eq!(bank.allowed, vec!["slate".to_string(), "crane".to_string()]);
+
+        std::fs::remove_file(&answers_path).unwrap();
+        std::fs::remove_file(&guesses_path).unwrap();
+        assert!(matches!(
+            WordBank::load_dual(&answers_path, &guesses_path),
+            Err(WordleError::Io(_))
+        ));
+    }
+
+    #[cfg(feature = "save")]
+    #[test]
+    fn test_save_words() {
+        let words = vec!["crane".to_string(), "slate".to_string()];
+        let path = std::env::temp_dir().join(format!("wordle_saved_{}.json", std::process::id()));
+
+        save_words(&words, &path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            r#"["crane","slate"]"#
+        );
+        assert_eq!(read_word_list(&path).unwrap(), words);
+
+        save_words_pretty(&words, &path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "[\n  \"crane\",\n  \"slate\"\n]"
+        );
+        assert_eq!(read_word_list(&path).unwrap(), words);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}